@@ -0,0 +1,98 @@
+//! Measures the code the derive generates for wide structs, and how long `cargo check` takes on it.
+//!
+//! Run with `cargo bench --bench expansion_size`. For each struct it writes a crate to the target directory, expands it
+//! with `-Zunpretty=expanded` (which `RUSTC_BOOTSTRAP=1` allows on stable) and then times `cargo check` on it. The
+//! number of tokens the derive generates is also checked by the `expansion_size` test of `typed-builder-macro`.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+fn write_crate(dir: &Path, width: usize, field_attr: &str) {
+    let mut fields = String::new();
+    for i in 0..width {
+        writeln!(fields, "    {field_attr} field{i}: u32,").unwrap();
+    }
+    let lib = format!(
+        "#![allow(dead_code)]\n\n#[derive(typed_builder::TypedBuilder)]\npub struct Foo<T> {{\n{fields}    #[builder(default)] t: Option<T>,\n}}\n"
+    );
+    let manifest = format!(
+        "[package]\nname = \"expansion-size\"\nversion = \"0.0.0\"\nedition = \"2024\"\n\n[dependencies]\ntyped-builder = {{ path = {:?} }}\n\n[workspace]\n",
+        env!("CARGO_MANIFEST_DIR"),
+    );
+    std::fs::create_dir_all(dir.join("src")).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+    std::fs::write(dir.join("src/lib.rs"), lib).unwrap();
+    // Reuse the resolved dependencies, so that this works offline.
+    std::fs::copy(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.lock"),
+        dir.join("Cargo.lock"),
+    )
+    .unwrap();
+}
+
+/// `target` is separate for expanding and for checking, since `RUSTC_BOOTSTRAP` makes cargo rebuild the dependencies.
+fn cargo(dir: &Path, target: &str, args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO"));
+    command
+        .args(args)
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", dir.parent().unwrap().join(target))
+        .env_remove("RUSTFLAGS");
+    command
+}
+
+/// The number of lines of the expanded crate.
+fn expanded_lines(dir: &Path) -> usize {
+    let output = cargo(
+        dir,
+        "target-expand",
+        &["rustc", "--quiet", "--lib", "--profile=check", "--", "-Zunpretty=expanded"],
+    )
+    .env("RUSTC_BOOTSTRAP", "1")
+    .output()
+    .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap().lines().count()
+}
+
+fn check_time(dir: &Path) -> Duration {
+    // Build the dependencies first, and only time the crate itself.
+    let status = cargo(dir, "target-check", &["check", "--quiet"]).status().unwrap();
+    assert!(status.success());
+    let lib = dir.join("src/lib.rs");
+    std::fs::write(&lib, std::fs::read(&lib).unwrap()).unwrap();
+    let start = Instant::now();
+    let status = cargo(dir, "target-check", &["check", "--quiet"]).status().unwrap();
+    assert!(status.success());
+    start.elapsed()
+}
+
+fn main() {
+    // `cargo test --all-targets` runs benchmarks once, without `--bench`, to check that they work.
+    let (widths, configurations): (&[usize], &[(&str, &str)]) = if std::env::args().any(|arg| arg == "--bench") {
+        (
+            &[25, 50, 100],
+            &[
+                ("required", ""),
+                ("default", "#[builder(default)]"),
+                ("default = expr", "#[builder(default = 1)]"),
+                ("default_where", "#[builder(default, default_where(T: Clone))]"),
+            ],
+        )
+    } else {
+        (&[2], &[("required", "")])
+    };
+
+    let base = Path::new(env!("CARGO_TARGET_TMPDIR")).join("expansion_size");
+    for &(description, field_attr) in configurations {
+        for &width in widths {
+            let dir = base.join("crate");
+            write_crate(&dir, width, field_attr);
+            let lines = expanded_lines(&dir);
+            let time = check_time(&dir);
+            println!("{description:>16}, {width:>3} fields: {lines:>6} lines, `cargo check` in {time:?}");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Compares the builder storages on a struct with many large fields.
+//!
+//! Run with `cargo bench --bench storage`, or with `cargo bench --profile dev --bench storage` to compare them without
+//! optimizations - where every setter still moves the whole builder, so the difference is smaller.
+
+#![allow(dead_code)]
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use typed_builder::TypedBuilder;
+
+macro_rules! many_fields_structs {
+    ($($field:ident)*) => {
+        #[derive(TypedBuilder)]
+        struct TupleStorage {
+            $(
+                #[builder(default = [0; 32])]
+                $field: [u64; 32],
+            )*
+        }
+
+        #[derive(TypedBuilder)]
+        #[builder(builder_type(storage = "in_place"))]
+        struct InPlaceStorage {
+            $(
+                #[builder(default = [0; 32])]
+                $field: [u64; 32],
+            )*
+        }
+
+        fn build_tuple_storage() -> TupleStorage {
+            TupleStorage::builder() $( .$field(black_box([1; 32])) )* .build()
+        }
+
+        fn build_in_place_storage() -> InPlaceStorage {
+            InPlaceStorage::builder() $( .$field(black_box([1; 32])) )* .build()
+        }
+    };
+}
+
+many_fields_structs!(
+    f00 f01 f02 f03 f04 f05 f06 f07 f08 f09 f10 f11 f12 f13 f14 f15 f16 f17 f18 f19
+    f20 f21 f22 f23 f24 f25 f26 f27 f28 f29 f30 f31 f32 f33 f34 f35 f36 f37 f38 f39
+    f40 f41 f42 f43 f44 f45 f46 f47 f48 f49 f50 f51 f52 f53 f54 f55 f56 f57 f58 f59
+);
+
+fn measure<T>(name: &str, iterations: u32, build: impl Fn() -> T) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(build());
+    }
+    let per_iteration = start.elapsed() / iterations;
+    println!("{name}: {per_iteration:?} per build");
+    per_iteration
+}
+
+fn main() {
+    // `cargo test --all-targets` runs benchmarks once, without `--bench`, to check that they work.
+    let iterations = if std::env::args().any(|arg| arg == "--bench") {
+        10_000
+    } else {
+        1
+    };
+
+    measure("tuple storage (60 fields of 256 bytes)", iterations, build_tuple_storage);
+    measure(
+        "in-place storage (60 fields of 256 bytes)",
+        iterations,
+        build_in_place_storage,
+    );
+}
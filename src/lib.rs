@@ -1,5 +1,8 @@
 #![no_std]
 
+#[cfg(feature = "from_env")]
+extern crate std;
+
 /// `TypedBuilder` is not a real type - deriving it will generate a `::builder()` method on your
 /// struct that will return a compile-time checked builder. Set the fields using setters with the
 /// same name as the struct's fields and call `.build()` when you are done to create your object.
@@ -55,6 +58,9 @@
 /// In addition to putting `#[derive(TypedBuilder)]` on a type, you can specify a `#[builder(...)]`
 /// attribute on the type, and on any fields in it.
 ///
+/// `#[cfg(...)]` and `#[cfg_attr(..., builder(...))]` are evaluated before the builder is generated,
+/// so fields that are configured out get no setters and don't need to be set.
+///
 /// On the **type**, the following values are permitted:
 ///
 /// - `doc`: enable documentation of the builder type. By default, the builder type is given
@@ -82,6 +88,29 @@
 ///     `build()` method of the builder type. Setting this implies `doc`.
 ///
 ///
+/// - The `builder_method(...)` subsection also has:
+///   - `args = [field1, field2, ...]`: make the builder method take these fields positionally, so
+///     that the builder it returns already has them set. Each field is taken the same way its
+///     setter takes it, so `into`, `strip_option` and `transform` apply.
+///
+///     ```
+///     use typed_builder::TypedBuilder;
+///
+///     #[derive(PartialEq, Debug, TypedBuilder)]
+///     #[builder(builder_method(args = [url]))]
+///     struct Client {
+///         #[builder(setter(into))]
+///         url: String,
+///         #[builder(default = 30)]
+///         timeout: u32,
+///     }
+///
+///     assert_eq!(
+///         Client::builder("https://example.com").timeout(5).build(),
+///         Client { url: "https://example.com".to_owned(), timeout: 5 },
+///     );
+///     ```
+///
 /// - The `build_method(...)` subsection also has:
 ///   - `into` or `into = ...`: change the output type of the builder. When a specific value/type
 ///     is set via the assignment, this will be the output type of the builder. If no specific
@@ -89,6 +118,34 @@
 ///     decide which type shall be constructed. In both cases an [`Into`] conversion is required to
 ///     be defined from the original type to the target type.
 ///
+///   - `impl_from`: make a builder that is ready to be built implement [`Into`] of the type the
+///     build method returns (or of the struct itself, when `into` is set without a type). This
+///     means that functions and `setter(into)` setters that accept `impl Into<Foo>` can be given
+///     the builder without calling the build method. This is opt-in since it conflicts with `From`
+///     impls of your own, and it is not available when `into` converts to a type parameter of the
+///     struct.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(TypedBuilder)]
+///   #[builder(build_method(impl_from))]
+///   struct RetryPolicy {
+///       attempts: u32,
+///       #[builder(default = 100)]
+///       delay_ms: u32,
+///   }
+///
+///   #[derive(TypedBuilder)]
+///   struct Client {
+///       #[builder(setter(into))]
+///       retry: RetryPolicy,
+///   }
+///
+///   let client = Client::builder().retry(RetryPolicy::builder().attempts(3)).build();
+///   assert_eq!(client.retry.delay_ms, 100);
+///   ```
+///
 /// - The `builder_type(...)` subsection also has:
 ///   - `attributes` - for adding attributes to the builder type. Note that the full attribute
 ///     syntax is required **inside** this section -
@@ -111,6 +168,50 @@
 ///     }
 ///     ```
 ///
+/// - The `builder_type(...)` subsection also has `debug`, for implementing [`Debug`](core::fmt::Debug)
+///   for the builder type. Set fields are shown with their values, and unset fields are shown as
+///   `<unset>` (or `<default>` if they have a default). Unlike deriving `Debug` with `attributes`,
+///   this only requires `Debug` for the types of the fields that are set.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(TypedBuilder)]
+///   #[builder(builder_type(debug))]
+///   struct Foo {
+///       x: i32,
+///       y: i32,
+///       #[builder(default)]
+///       z: i32,
+///   }
+///
+///   assert_eq!(format!("{:?}", Foo::builder().x(1)), "FooBuilder { x: 1, y: <unset>, z: <default> }");
+///   ```
+///
+/// - The `builder_type(...)` subsection also has `storage = "..."`, for choosing how the builder
+///   keeps the values of the fields:
+///   - `"tuple"` (the default): a tuple of the set values. Every setter moves all the fields from
+///     the old builder to the new one.
+///   - `"in_place"`: a hidden struct with an `Option` per field. Each setter writes its own field
+///     in place and moves the struct as a whole, while the builder's type keeps tracking which
+///     fields are set. This is cheaper for structs with many or large fields. The builder's type
+///     parameter stays the same. `setter(generic)` and `external_mutators` are not supported with
+///     it.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(TypedBuilder)]
+///   #[builder(builder_type(storage = "in_place"))]
+///   struct Foo {
+///       x: [u8; 1024],
+///       #[builder(default = [0; 1024])]
+///       y: [u8; 1024],
+///   }
+///
+///   let foo = Foo::builder().x([1; 1024]).build();
+///   ```
+///
 /// - `field_defaults(...)` is structured like the `#[builder(...)]` attribute you can put on the
 ///   fields and sets default options for fields of the type. If specific field need to revert some
 ///   options to the default defaults they can prepend `!` to the option they need to revert, and
@@ -145,6 +246,205 @@
 /// - `mutators(...)` takes functions, that can mutate fields inside of the builder.
 ///   See [mutators](#mutators) for details.
 ///
+/// - `external_mutators`: allow defining mutators in separate `impl` blocks with
+///   [`#[typed_builder::mutators]`](macro@mutators), anywhere in the same crate.
+///
+/// - `new` or `new(...)`: generate a constructor that takes the required fields - in the same way
+///   their setters take them - and sets all the other fields to their defaults, exactly as
+///   `build()` would. `new(...)` accepts `vis = "..."`, `name = ...` and `doc = "..."` like the
+///   `builder_method(...)` subsection, and the constructor is named `new` by default.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(PartialEq, Debug, TypedBuilder)]
+///   #[builder(new)]
+///   struct Foo {
+///       #[builder(setter(into))]
+///       name: String,
+///       #[builder(default = name.len())]
+///       len: usize,
+///   }
+///
+///   assert_eq!(Foo::new("foo"), Foo { name: "foo".to_owned(), len: 3 });
+///   ```
+///
+/// - `step`: the required fields must be set in the order they are declared. Each state of the
+///   builder is a concrete type, which offers only the next required setter - plus the setters of
+///   the optional fields, which can be called in any state and override earlier values. Required
+///   fields cannot be `overridable` or `resettable`, presets can only set optional fields, and
+///   the required fields in `builder_method(args = [...])` must be the first ones, in order.
+///   `default_where`, `setter(generic)`, `getters`, `via_mutators` and mutators are not
+///   supported.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(TypedBuilder)]
+///   #[builder(step)]
+///   struct Foo {
+///       x: i32,
+///       #[builder(default)]
+///       y: i32,
+///       z: i32,
+///   }
+///
+///   Foo::builder().x(1).y(2).z(3).build();
+///   let builder: FooBuilder<((i32,), Option<i32>, ())> = Foo::builder().y(2).x(1);
+///   builder.z(3).build();
+///   ```
+///
+/// - `const`: make `builder()`, the setters and `build()` `const fn`s, so that `const` and `static`
+///   items can use the builder. The defaults must be const expressions. As in other builders they
+///   can refer to the earlier fields as references - but a `const fn` cannot call operators on
+///   references, so they need to be dereferenced (e.g. `default = *width * 2`). Options that call
+///   traits or closures - `into`, `convert`, `transform`, `default_where`, `setter(generic)`,
+///   `overridable`, `resettable`, `getters`, mutators, presets, `builder_type(debug)` and
+///   `build_method(into)` - are not supported.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(TypedBuilder)]
+///   #[builder(const)]
+///   struct Register {
+///       address: u32,
+///       #[builder(default = 4)]
+///       width: u8,
+///       #[builder(default = None, setter(strip_option))]
+///       name: Option<&'static str>,
+///       #[builder(default = *width * 8)]
+///       bits: u8,
+///   }
+///
+///   static REGISTERS: [Register; 2] = [
+///       Register::builder().address(0x10).build(),
+///       Register::builder().address(0x14).width(2).name("status").build(),
+///   ];
+///
+///   assert_eq!(REGISTERS[0].width, 4);
+///   assert_eq!(REGISTERS[0].bits, 32);
+///   assert_eq!(REGISTERS[1].name, Some("status"));
+///   ```
+///
+/// - `preset(name = ..., field = ..., ...)`: generate a method with the given name that creates a
+///   builder with the listed fields already set, by passing the values to their setters. Fields
+///   that the preset does not set remain required (or defaulted) as usual. A field named `name`
+///   can be set with `r#name = ...`. The preset values can be replaced once by calling the
+///   fields' setters on the builder the preset creates. The builder created by `builder()` is not
+///   affected - setting a field twice is still an error there.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(PartialEq, Debug, TypedBuilder)]
+///   #[builder(preset(name = local, host = "localhost", port = 8080))]
+///   struct Server {
+///       #[builder(setter(into))]
+///       host: String,
+///       port: u16,
+///       workers: usize,
+///   }
+///
+///   assert_eq!(
+///       Server::local().port(9090).workers(4).build(),
+///       Server { host: "localhost".to_owned(), port: 9090, workers: 4 },
+///   );
+///   ```
+///
+/// - `deserialize`: implement serde's `Deserialize` for the struct, using the same rules the
+///   builder uses. Fields that are missing from the input get their `default`, and if any required
+///   field is missing the error names all the missing fields. Fields with a single-parameter
+///   `transform` are deserialized from the parameter's type and then transformed. Fields that are
+///   `via_mutators` are not deserialized - they get their initial value. Since any field may be
+///   missing, the `Deserialize` implementation requires the bounds of all the `default_where`s.
+///   Requires the `serde` feature.
+///
+///   ```
+///   # #[cfg(feature = "serde")] {
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(PartialEq, Debug, TypedBuilder)]
+///   #[builder(deserialize)]
+///   struct Config {
+///       name: String,
+///       #[builder(default = 8080)]
+///       port: u16,
+///       #[builder(default = format!("https://{name}:{port}"))]
+///       url: String,
+///   }
+///
+///   let config: Config = serde_json::from_str(r#"{"name": "localhost"}"#).unwrap();
+///   assert_eq!(config, Config::builder().name("localhost".to_owned()).build());
+///   assert_eq!(config.url, "https://localhost:8080");
+///
+///   let error = serde_json::from_str::<Config>(r#"{"port": 80}"#).unwrap_err();
+///   assert_eq!(error.to_string(), "missing field `name`");
+///   # }
+///   ```
+///
+/// - `from_env` or `from_env(prefix = "...")`: generate a `from_env()` method that creates the
+///   struct from environment variables, and returns an `EnvError` listing all the missing and
+///   unparsable variables if it fails. The name of each variable is the prefix followed by the
+///   uppercased field name, unless overridden with the field's `from_env(name = "...")`. The
+///   values are parsed with [`FromStr`](core::str::FromStr) - after `strip_option`, or into the
+///   parameter of a single-parameter `transform` - and fields with a `default` get it when their
+///   variable is not set. Fields that are `via_mutators` are not read - they get their initial
+///   value. Requires the `from_env` feature.
+///
+///   ```
+///   # #[cfg(feature = "from_env")] {
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(PartialEq, Debug, TypedBuilder)]
+///   #[builder(from_env(prefix = "APP_"))]
+///   struct Config {
+///       #[builder(from_env(name = "APP_HOST_NAME"))]
+///       host: String,
+///       #[builder(default = 8080)]
+///       port: u16,
+///   }
+///
+///   let error = Config::from_env().unwrap_err();
+///   assert_eq!(error.to_string(), "missing environment variable `APP_HOST_NAME`");
+///   # }
+///   ```
+///
+/// - `patch` or `patch(...)`: generate a `FooPatch` struct with an `Option` of each field's type
+///   for every field that has a setter, for combining layers of configuration (e.g. defaults, a
+///   file, the environment and the command line) that may each set any of the fields.
+///   `a.merge(b)` takes the fields `b` sets and the rest from `a`, and `build()` creates the
+///   struct using the same rules the builder uses - or returns a [`MissingFields`] naming all the
+///   required fields that are not set. `patch(...)` accepts `vis = "..."`, `name = ...` and
+///   `doc = "..."` like the `builder_type(...)` subsection, and `attributes(...)` for adding
+///   attributes like `#[derive(Clone, Debug)]` to the patch. Its fields have the visibility of
+///   the setters. They hold the fields' own types, ignoring setter options like `into` and
+///   `strip_option` - so the patch field of a `strip_option` field is an `Option<Option<T>>`.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(PartialEq, Debug, TypedBuilder)]
+///   #[builder(patch)]
+///   struct Config {
+///       host: String,
+///       #[builder(default = 8080)]
+///       port: u16,
+///       #[builder(default)]
+///       verbose: bool,
+///   }
+///
+///   let file = ConfigPatch { host: Some("example.com".to_owned()), port: Some(80), ..Default::default() };
+///   let cli = ConfigPatch { verbose: Some(true), ..Default::default() };
+///   assert_eq!(
+///       file.merge(cli).build().unwrap(),
+///       Config { host: "example.com".to_owned(), port: 80, verbose: true },
+///   );
+///
+///   let error = ConfigPatch::default().build().unwrap_err();
+///   assert_eq!(error.to_string(), "missing field `host`");
+///   ```
+///
 /// On each **field**, the following values are permitted:
 ///
 /// - `default`: make the field optional, defaulting to `Default::default()`. This requires that
@@ -183,6 +483,30 @@
 /// - `mutators(...)` takes functions, that can mutate fields inside of the builder.
 ///   Mutators specified on a field, mark this field as required, see [mutators](#mutators) for details.
 ///
+/// - `from_env(name = "...")`: set the name of the environment variable the field is read from
+///   by the `from_env()` method generated with the type-level `from_env`.
+///
+/// - `getters`: generate `get_<field>()` and `<field>_mut()` methods on the builder, which return a
+///   reference and a mutable reference to the field's value. These methods are only available
+///   once the field is set (or, for `via_mutators` fields, always). Use
+///   `#[builder(field_defaults(getters))]` to generate getters for all the fields.
+///
+///   ```
+///   use typed_builder::TypedBuilder;
+///
+///   #[derive(PartialEq, Debug, TypedBuilder)]
+///   struct Foo {
+///       #[builder(getters)]
+///       x: i32,
+///       y: i32,
+///   }
+///
+///   let mut builder = Foo::builder().x(1);
+///   *builder.x_mut() += 1;
+///   let y = *builder.get_x() * 10;
+///   assert_eq!(builder.y(y).build(), Foo { x: 2, y: 20 });
+///   ```
+///
 /// - `setter(...)`: settings for the field setters. The following values are permitted inside:
 ///
 ///   - `doc = "..."`: sets the documentation for the field's setter on the builder type. This will be
@@ -196,6 +520,36 @@
 ///     Note that this conversion interferes with Rust's type inference and integer literal
 ///     detection, so this may reduce ergonomics if the field type is generic or an unsigned integer.
 ///
+///   - `convert = Trait::method`: like `into`, but with a conversion trait of your own. The setter
+///     accepts `impl Trait` and calls `method` - which must take `self` by value and return the
+///     field's type - on it. Combined with `field_defaults`, this applies the same conversion to
+///     many fields without writing a `transform` for each of them:
+///
+///     ```
+///     use std::time::Duration;
+///     use typed_builder::TypedBuilder;
+///
+///     trait IntoDuration {
+///         fn into_duration(self) -> Duration;
+///     }
+///
+///     impl IntoDuration for u64 {
+///         fn into_duration(self) -> Duration {
+///             Duration::from_secs(self)
+///         }
+///     }
+///
+///     #[derive(TypedBuilder)]
+///     #[builder(field_defaults(setter(convert = IntoDuration::into_duration)))]
+///     struct Timeouts {
+///         connect: Duration,
+///         read: Duration,
+///     }
+///
+///     let timeouts = Timeouts::builder().connect(5).read(30).build();
+///     assert_eq!(timeouts.read, Duration::from_secs(30));
+///     ```
+///
 ///   - `strip_option`: for `Option<...>` fields only, this makes the setter wrap its argument with
 ///     `Some(...)`, relieving the caller from having to do this. Note that with this setting on
 ///     one cannot set the field to `None` with the setter - so the only way to get it to be `None`
@@ -236,6 +590,24 @@
 ///     the boolean value. The name given to the fallback method adds another method to the builder
 ///     without where the bool value can be specified.
 ///
+///   - `strip_bool(negated = field_off)`: for `bool` fields only. As above, and also adds another
+///     method to the builder that receives no arguments and sets the field to `false`. Since both
+///     values can be set this way, the field may have an explicit `default` - such as `true`:
+///
+///     ```
+///     use typed_builder::TypedBuilder;
+///
+///     #[derive(PartialEq, Debug, TypedBuilder)]
+///     struct Client {
+///         #[builder(default = true, setter(strip_bool(negated = insecure)))]
+///         verify_tls: bool,
+///     }
+///
+///     assert_eq!(Client::builder().build(), Client { verify_tls: true });
+///     assert_eq!(Client::builder().insecure().build(), Client { verify_tls: false });
+///     assert_eq!(Client::builder().verify_tls().build(), Client { verify_tls: true });
+///     ```
+///
 ///   - `transform = |param1: Type1, param2: Type2 ...| expr`: this makes the setter accept
 ///     `param1: Type1, param2: Type2 ...` instead of the field type itself. The parameters are
 ///     transformed into the field type using the expression `expr`. The transformation is performed
@@ -263,6 +635,138 @@
 ///     `suffix = "_value"` results in setters like `x_value` or `y_value`. This option is combinable
 ///     with `prefix = "..."`.
 ///
+///   - `name = ...` sets the name of the setter method, regardless of the field's name and of
+///     `prefix` and `suffix`.
+///
+///   - `aliases = [...]` generates additional setter methods with the given names, which behave
+///     exactly like the setter but are `#[deprecated]`. This keeps the old setter names working
+///     for a while after renaming a field or its setter:
+///
+///     ```
+///     use typed_builder::TypedBuilder;
+///
+///     #[derive(PartialEq, Debug, TypedBuilder)]
+///     struct Foo {
+///         #[builder(setter(name = with_timeout, aliases = [timeout_ms]))]
+///         timeout: u32,
+///     }
+///
+///     assert_eq!(Foo::builder().with_timeout(5).build(), Foo { timeout: 5 });
+///     #[allow(deprecated)]
+///     let foo = Foo::builder().timeout_ms(5).build();
+///     assert_eq!(foo, Foo { timeout: 5 });
+///     ```
+///
+///   - `overridable`: allow calling the setter even after the field was already set, with the
+///     last value winning. Without this, setting a field twice is a compile-time error. This is
+///     useful for layered configuration, where a base function sets some values and callers may
+///     override them:
+///
+///     ```
+///     use typed_builder::TypedBuilder;
+///
+///     #[derive(PartialEq, Debug, TypedBuilder)]
+///     struct Foo {
+///         #[builder(setter(overridable))]
+///         x: i32,
+///         y: i32,
+///     }
+///
+///     assert_eq!(Foo::builder().x(1).y(2).x(3).build(), Foo { x: 3, y: 2 });
+///     ```
+///
+///     Use `#[builder(field_defaults(setter(overridable)))]` to make all the setters overridable.
+///
+///   - `resettable`: generate a `clear_<field>()` method that returns the field to its unset
+///     state, so that its default applies again and its setter can be called once more. Together
+///     with cloning the builder, this allows sharing a partially configured builder and stripping
+///     out the parts a specific call site does not want:
+///
+///     ```
+///     use typed_builder::TypedBuilder;
+///
+///     #[derive(PartialEq, Debug, TypedBuilder)]
+///     struct Foo {
+///         x: i32,
+///         #[builder(default = 10, setter(resettable))]
+///         timeout: u32,
+///     }
+///
+///     let template = Foo::builder().x(1).timeout(5);
+///     assert_eq!(template.clone().build(), Foo { x: 1, timeout: 5 });
+///     assert_eq!(template.clone().clear_timeout().build(), Foo { x: 1, timeout: 10 });
+///     assert_eq!(template.clear_timeout().timeout(20).build(), Foo { x: 1, timeout: 20 });
+///     ```
+///
+///   - `vis = "..."`: sets the visibility of the field's setter - and of its strip fallback,
+///     `clear_<field>()` and getter methods, and of the mutators declared on the field without a
+///     visibility of their own. The default is `pub`. This allows exposing a builder while keeping
+///     some of its knobs internal to the crate.
+///
+///   - `inherit_vis`: use the visibility of the field itself, in the same way as `vis = "..."`.
+///     Use `#[builder(field_defaults(setter(inherit_vis)))]` to make the setters of private
+///     fields private:
+///
+///     ```
+///     mod config {
+///         use typed_builder::TypedBuilder;
+///
+///         #[derive(TypedBuilder)]
+///         #[builder(field_defaults(setter(inherit_vis)))]
+///         pub struct Config {
+///             pub name: String,
+///             #[builder(default = 4)]
+///             threads: usize,
+///             #[builder(default, setter(vis = "pub(crate)"))]
+///             pub debug: bool,
+///         }
+///
+///         impl Config {
+///             pub fn threads(&self) -> usize {
+///                 self.threads
+///             }
+///         }
+///     }
+///
+///     let config = config::Config::builder().name("app".to_owned()).debug(true).build();
+///     assert_eq!(config.threads(), 4);
+///     ```
+///
+///   - `generic`: for fields whose type is one of the struct's type parameters only, this makes
+///     the setter accept a value of any type and change the type parameter of the builder to it -
+///     as long as the type satisfies the struct's bounds, and the other fields that use the type
+///     parameter were not set yet. When the type parameter has a default, the builder starts with
+///     it, so a defaulted field does not require specifying the type. This also means that the
+///     builder method is only implemented for the default - `Client::builder()` and
+///     `Client::<NoAuth>::builder()` below work, but `Client::<Bearer>::builder()` does not, and
+///     the type is set by calling the setter instead. Combine with
+///     `default, default_where(T: Default)` to default to the type parameter's default:
+///
+///     ```
+///     use typed_builder::TypedBuilder;
+///
+///     trait Auth {}
+///
+///     #[derive(PartialEq, Debug, Default)]
+///     struct NoAuth;
+///     impl Auth for NoAuth {}
+///
+///     #[derive(PartialEq, Debug)]
+///     struct Bearer(&'static str);
+///     impl Auth for Bearer {}
+///
+///     #[derive(PartialEq, Debug, TypedBuilder)]
+///     struct Client<T: Auth = NoAuth> {
+///         #[builder(default, default_where(T: Default), setter(generic))]
+///         auth: T,
+///         url: &'static str,
+///     }
+///
+///     assert_eq!(Client::builder().url("x").build(), Client { auth: NoAuth, url: "x" });
+///     assert_eq!(Client::<NoAuth>::builder().url("x").build(), Client { auth: NoAuth, url: "x" });
+///     assert_eq!(Client::builder().auth(Bearer("token")).url("x").build().auth, Bearer("token"));
+///     ```
+///
 ///   - `mutable_during_default_resolution`: when expressions in `default = ...` field attributes
 ///     are evaluated, this field will be mutable, allowing earlier-defined fields to be mutated by
 ///     later-defined fields.
@@ -279,6 +783,69 @@
 /// Mutators on a field, result in them automatically making the field required, i.e., it needs to be
 /// marked as `via_mutators`, or its setter be called. Appart from that, they behave identically.
 ///
+/// Mutators that return a value make the builder method return it alongside the builder:
+///
+/// - A mutator returning `()` (or nothing) makes the method return the builder.
+/// - A mutator returning `Result<(), E>` makes the method return `Result<FooBuilder<...>, E>`, so
+///   mutators can validate their input.
+/// - A mutator returning `Result<R, E>` makes the method return `Result<(FooBuilder<...>, R), E>`.
+/// - A mutator returning any other type `R` makes the method return `(FooBuilder<...>, R)`.
+///
+/// `Result` is recognized by name, so aliases like `std::io::Result<()>` work as well.
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, Debug, TypedBuilder)]
+/// #[builder(mutators(
+///     fn add_user(&mut self, user: &str) -> Result<(), String> {
+///         if user.is_empty() {
+///             return Err("empty user name".to_owned());
+///         }
+///         self.users.push(user.to_owned());
+///         Ok(())
+///     }
+/// ))]
+/// struct Foo {
+///     #[builder(via_mutators)]
+///     users: Vec<String>,
+/// }
+///
+/// assert_eq!(
+///     Foo::builder().add_user("alice").unwrap().add_user("bob").unwrap().build(),
+///     Foo { users: vec!["alice".to_owned(), "bob".to_owned()] });
+/// assert!(Foo::builder().add_user("").is_err());
+/// ```
+///
+/// A mutator can also set fields that were not set yet, by declaring them with
+/// `#[mutator(sets = [field1, field2, ...])]`. The mutator is only available while these fields
+/// are unset, and instead of assigning them it returns their values - the value of the field
+/// itself for a single field, or a tuple of the values in the order of the `sets` list - so the
+/// compiler checks that it sets all of them. It may also return a `Result` of these values, making
+/// the builder method fallible. The builder it returns has these fields marked as set - so calling
+/// their setters afterwards is a compile error, and `build` does not complain about them missing.
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, Debug, TypedBuilder)]
+/// #[builder(mutators(
+///     #[mutator(sets = [cert, key])]
+///     fn tls_from_pem(&mut self, pem: &str) -> (String, String) {
+///         let (cert, key) = pem.split_once('|').unwrap();
+///         (cert.to_owned(), key.to_owned())
+///     }
+/// ))]
+/// struct Tls {
+///     cert: String,
+///     key: String,
+/// }
+///
+/// assert_eq!(
+///     Tls::builder().tls_from_pem("CERT|KEY").build(),
+///     Tls { cert: "CERT".to_owned(), key: "KEY".to_owned() });
+/// ```
+///
 /// ```
 /// use typed_builder::TypedBuilder;
 ///
@@ -316,6 +883,368 @@
 /// ```
 pub use typed_builder_macro::TypedBuilder;
 
+/// Define [mutators](TypedBuilder#mutators) in a separate `impl` block instead of inside the
+/// `#[builder(mutators(...))]` attribute.
+///
+/// The struct must opt into this with `#[builder(external_mutators)]`, which generates the hidden
+/// macro this attribute calls. The argument is the path to the struct that derives `TypedBuilder`. The type name of the `impl`
+/// block itself is only a label and is not used for anything. Each function in the block becomes a
+/// mutator, and supports the same `#[mutator(...)]` attributes as mutators defined in the derive.
+///
+/// The block can live in a different module than the struct (e.g.
+/// `#[typed_builder::mutators(super::Foo)]`), as long as it is in the same crate and the builder
+/// type is visible from there. Note that the types of the struct's fields are resolved in the
+/// module of the `impl` block, so they need to be in scope there.
+///
+/// ```
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(PartialEq, Debug, TypedBuilder)]
+/// #[builder(external_mutators)]
+/// struct Foo {
+///     x: i32,
+///     #[builder(via_mutators)]
+///     items: Vec<i32>,
+/// }
+///
+/// #[typed_builder::mutators(Foo)]
+/// impl FooMutators {
+///     fn add_item(&mut self, item: i32) {
+///         self.items.push(item);
+///     }
+///
+///     #[mutator(requires = [x])]
+///     fn add_x(&mut self) {
+///         self.items.push(self.x);
+///     }
+/// }
+///
+/// assert_eq!(
+///     Foo::builder().add_item(1).x(2).add_x().build(),
+///     Foo { x: 2, items: vec![1, 2] });
+/// ```
+pub use typed_builder_macro::mutators;
+
+/// Create a struct that derives `TypedBuilder` with struct literal syntax, using its builder.
+///
+/// `build!(Foo { x: 1, y })` expands to `<Foo>::builder().x(1).y(y).build()`, so the setters'
+/// conversions (`into`, `strip_option`, `transform`) apply, the fields that are not listed get
+/// their defaults, and missing required fields are compile errors. The struct literal may end with
+/// `..defaults` to make it clear that the rest of the fields get their defaults - no other `..`
+/// expression is allowed.
+///
+/// Each value is passed as the only argument of its setter, and the builder method, the setters and
+/// the build method must keep their default names. Nested structs can be created with nested
+/// `build!` invocations.
+///
+/// ```
+/// use typed_builder::{TypedBuilder, build};
+///
+/// #[derive(PartialEq, Debug, TypedBuilder)]
+/// struct Server {
+///     #[builder(setter(into))]
+///     host: String,
+///     #[builder(default = 80)]
+///     port: u16,
+///     tls: Tls,
+/// }
+///
+/// #[derive(PartialEq, Debug, TypedBuilder)]
+/// struct Tls {
+///     #[builder(default, setter(strip_option, into))]
+///     cert: Option<String>,
+/// }
+///
+/// let server = build!(Server {
+///     host: "localhost",
+///     tls: build!(Tls { cert: "cert.pem" }),
+///     ..defaults
+/// });
+/// assert_eq!(server, Server::builder().host("localhost").tls(Tls { cert: Some("cert.pem".to_owned()) }).build());
+/// assert_eq!(server.port, 80);
+/// ```
+pub use typed_builder_macro::build;
+
+#[doc(hidden)]
+pub use typed_builder_macro::__mutators_impl;
+
+/// The error returned by the `from_env()` method generated with `#[builder(from_env)]`.
+///
+/// It lists all the problems with the environment variables - not just the first one.
+#[cfg(feature = "from_env")]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EnvError {
+    /// Environment variables of required fields that are not set.
+    pub missing: std::vec::Vec<&'static str>,
+    /// Environment variables that are set but could not be parsed, and the parse errors.
+    pub invalid: std::vec::Vec<(&'static str, std::string::String)>,
+}
+
+#[cfg(feature = "from_env")]
+impl core::fmt::Display for EnvError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut separator = "";
+        for name in &self.missing {
+            write!(f, "{separator}missing environment variable `{name}`")?;
+            separator = "; ";
+        }
+        for (name, error) in &self.invalid {
+            write!(f, "{separator}invalid environment variable `{name}`: {error}")?;
+            separator = "; ";
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "from_env")]
+impl core::error::Error for EnvError {}
+
+/// The required fields that are missing when building a struct dynamically - by the `build()` method of the patch
+/// generated with `#[builder(patch)]`, or by deserializing with `#[builder(deserialize)]`.
+///
+/// It holds the names of all the struct's required fields and which of them are missing, so structs built this way can
+/// have at most 128 required fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingFields {
+    required: &'static [&'static str],
+    missing: u128,
+}
+
+impl MissingFields {
+    #[doc(hidden)]
+    pub const fn __new(required: &'static [&'static str], missing: u128) -> Self {
+        Self { required, missing }
+    }
+
+    /// The names of the missing fields, in declaration order.
+    pub fn missing(&self) -> impl Iterator<Item = &'static str> + Clone + '_ {
+        self.required
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.missing & (1 << index) != 0)
+            .map(|(_, name)| *name)
+    }
+}
+
+impl core::fmt::Display for MissingFields {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut missing = self.missing();
+        let count = missing.clone().count();
+        f.write_str(if count == 1 { "missing field" } else { "missing fields" })?;
+        if let Some(first) = missing.next() {
+            write!(f, " `{first}`")?;
+        }
+        for name in missing {
+            write!(f, ", `{name}`")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for MissingFields {}
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use serde;
+
+    /// Shown in the builder's `Debug` output for fields that are not set.
+    pub struct UnsetField(pub &'static str);
+
+    impl core::fmt::Debug for UnsetField {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    /// Implemented by the builder's field slots - `()` for unset fields, `(T,)` for set fields and `Option<T>` for the
+    /// optional fields of `step` builders - so that the builder's `Debug` implementation only requires `Debug` for the
+    /// types of the set fields.
+    pub trait DebugFieldSlot {
+        fn debug_slot<'a>(&'a self, unset: &'a UnsetField) -> &'a dyn core::fmt::Debug;
+    }
+
+    impl DebugFieldSlot for () {
+        fn debug_slot<'a>(&'a self, unset: &'a UnsetField) -> &'a dyn core::fmt::Debug {
+            unset
+        }
+    }
+
+    impl<T: core::fmt::Debug> DebugFieldSlot for (T,) {
+        fn debug_slot<'a>(&'a self, _unset: &'a UnsetField) -> &'a dyn core::fmt::Debug {
+            &self.0
+        }
+    }
+
+    impl<T: core::fmt::Debug> DebugFieldSlot for Option<T> {
+        fn debug_slot<'a>(&'a self, unset: &'a UnsetField) -> &'a dyn core::fmt::Debug {
+            match self {
+                Some(value) => value,
+                None => unset,
+            }
+        }
+    }
+
+    impl<T: core::fmt::Debug> DebugFieldSlot for Preset<T> {
+        fn debug_slot<'a>(&'a self, _unset: &'a UnsetField) -> &'a dyn core::fmt::Debug {
+            &self.0
+        }
+    }
+
+    /// The slot of a field that a preset set. Unlike `(T,)`, the field's setter accepts it, so that the preset's value
+    /// can be overridden.
+    #[derive(Clone)]
+    pub struct Preset<T>(pub T);
+
+    /// Implemented by the slots of set fields - `(T,)` and `Preset<T>` - for the impls that need fields which presets
+    /// set to be set. `S` is the struct being built, which lets the compiler see that `()` does not implement this
+    /// trait, so that these impls do not overlap with the ones for the unset field.
+    pub trait SetSlot<S, T> {
+        fn get(&self) -> &T;
+        fn get_mut(&mut self) -> &mut T;
+        fn into_value(self) -> T;
+    }
+
+    impl<S, T> SetSlot<S, T> for (T,) {
+        fn get(&self) -> &T {
+            &self.0
+        }
+
+        fn get_mut(&mut self) -> &mut T {
+            &mut self.0
+        }
+
+        fn into_value(self) -> T {
+            self.0
+        }
+    }
+
+    impl<S, T> SetSlot<S, T> for Preset<T> {
+        fn get(&self) -> &T {
+            &self.0
+        }
+
+        fn get_mut(&mut self) -> &mut T {
+            &mut self.0
+        }
+
+        fn into_value(self) -> T {
+            self.0
+        }
+    }
+
+    /// Implemented by the slots that the setter of a field which presets set accepts - `()` and `Preset<T>` - so that
+    /// only the builders created by presets can override the field. `S` is the struct being built, like in [`SetSlot`].
+    pub trait PresetOrUnsetSlot<S, T> {}
+
+    impl<S, T> PresetOrUnsetSlot<S, T> for () {}
+
+    impl<S, T> PresetOrUnsetSlot<S, T> for Preset<T> {}
+
+    /// The value of an in-place slot that the builder's type says is set. `Option::expect` lets `const` builders use it.
+    pub const fn set_slot<T>(slot: Option<T>) -> T {
+        slot.expect("the builder's type says the field is set")
+    }
+
+    /// Clones the `Option<T>` slots of `builder_type(storage = "in_place")` and `const` builders, so that cloning the
+    /// builder only requires `Clone` for the types of the set fields.
+    pub trait CloneInPlaceSlot<T> {
+        fn clone_in_place(slot: &Option<T>) -> Option<T>;
+    }
+
+    impl<T> CloneInPlaceSlot<T> for () {
+        fn clone_in_place(_slot: &Option<T>) -> Option<T> {
+            None
+        }
+    }
+
+    impl<T: Clone> CloneInPlaceSlot<T> for (T,) {
+        fn clone_in_place(slot: &Option<T>) -> Option<T> {
+            slot.clone()
+        }
+    }
+
+    impl<T: Clone> CloneInPlaceSlot<T> for Option<T> {
+        fn clone_in_place(slot: &Option<T>) -> Option<T> {
+            slot.clone()
+        }
+    }
+
+    impl<T: Clone> CloneInPlaceSlot<T> for Preset<T> {
+        fn clone_in_place(slot: &Option<T>) -> Option<T> {
+            slot.clone()
+        }
+    }
+
+    /// Like [`DebugFieldSlot`], for the `Option<T>` slots of `builder_type(storage = "in_place")` builders.
+    pub trait DebugInPlaceSlot<T> {
+        fn debug_in_place<'a>(slot: &'a Option<T>, unset: &'a UnsetField) -> &'a dyn core::fmt::Debug;
+    }
+
+    impl<T> DebugInPlaceSlot<T> for () {
+        fn debug_in_place<'a>(_slot: &'a Option<T>, unset: &'a UnsetField) -> &'a dyn core::fmt::Debug {
+            unset
+        }
+    }
+
+    impl<T: core::fmt::Debug> DebugInPlaceSlot<T> for (T,) {
+        fn debug_in_place<'a>(slot: &'a Option<T>, unset: &'a UnsetField) -> &'a dyn core::fmt::Debug {
+            match slot {
+                Some(value) => value,
+                None => unset,
+            }
+        }
+    }
+
+    impl<T: core::fmt::Debug> DebugInPlaceSlot<T> for Option<T> {
+        fn debug_in_place<'a>(slot: &'a Option<T>, unset: &'a UnsetField) -> &'a dyn core::fmt::Debug {
+            match slot {
+                Some(value) => value,
+                None => unset,
+            }
+        }
+    }
+
+    impl<T: core::fmt::Debug> DebugInPlaceSlot<T> for Preset<T> {
+        fn debug_in_place<'a>(slot: &'a Option<T>, unset: &'a UnsetField) -> &'a dyn core::fmt::Debug {
+            match slot {
+                Some(value) => value,
+                None => unset,
+            }
+        }
+    }
+
+    /// Read and parse an environment variable for `from_env()`, recording any problem in `error`.
+    #[cfg(feature = "from_env")]
+    pub fn env_var<T>(name: &'static str, required: bool, error: &mut crate::EnvError) -> Option<T>
+    where
+        T: core::str::FromStr,
+        T::Err: core::fmt::Display,
+    {
+        use std::string::ToString;
+
+        match std::env::var(name) {
+            Ok(value) => match value.parse() {
+                Ok(value) => Some(value),
+                Err(parse_error) => {
+                    error.invalid.push((name, parse_error.to_string()));
+                    None
+                }
+            },
+            Err(std::env::VarError::NotPresent) => {
+                if required {
+                    error.missing.push(name);
+                }
+                None
+            }
+            Err(std::env::VarError::NotUnicode(_)) => {
+                error.invalid.push((name, "not valid unicode".to_string()));
+                None
+            }
+        }
+    }
+}
+
 #[doc(hidden)]
 pub trait NextFieldDefault<TypedBuilderExistingFields> {
     type Output;
@@ -493,6 +1422,42 @@ pub trait NextFieldDefault<TypedBuilderExistingFields> {
 /// }
 /// ```
 ///
+/// `convert` replaces `into`, so they cannot be combined
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(into, convert = ToString::to_string))]
+///     value: String,
+/// }
+/// ```
+///
+/// `negated` is only supported for `strip_bool`
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(setter(strip_option(negated = no_value)))]
+///     value: Option<i32>,
+/// }
+/// ```
+///
+/// Without a negated setter, `strip_bool` does not allow a default
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(default = true, setter(strip_bool))]
+///     value: bool,
+/// }
+/// ```
+///
 /// Handling alternative propertes for `strip_bool`
 ///
 /// ```compile_fail
@@ -504,4 +1469,282 @@ pub trait NextFieldDefault<TypedBuilderExistingFields> {
 ///     value: bool,
 /// }
 /// ```
+///
+/// A mutator that sets a field is not available once the field is set:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(mutators(
+///     #[mutator(sets = [value])]
+///     fn set_value(&mut self) -> i32 {
+///         1
+///     }
+/// ))]
+/// struct Foo {
+///     value: i32,
+/// }
+///
+/// Foo::builder().value(1).set_value().build();
+/// ```
+///
+/// Mutators can only be defined outside the derive if the struct opts into it:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(via_mutators)]
+///     items: Vec<i32>,
+/// }
+///
+/// #[typed_builder::mutators(Foo)]
+/// impl FooMutators {
+///     fn add_item(&mut self, item: i32) {
+///         self.items.push(item);
+///     }
+/// }
+/// ```
+///
+/// The builder's fields stay private to the module of the struct:
+///
+/// ```compile_fail
+/// mod config {
+///     #[derive(typed_builder::TypedBuilder)]
+///     #[builder(external_mutators)]
+///     pub struct Foo {
+///         pub x: i32,
+///     }
+/// }
+///
+/// let _ = config::Foo::builder().x(1).fields;
+/// ```
+///
+/// A mutator that sets fields must return the values of all of them:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(mutators(
+///     #[mutator(sets = [a, b])]
+///     fn set_both(&mut self) -> (i32,) {
+///         (1,)
+///     }
+/// ))]
+/// struct Foo {
+///     a: i32,
+///     b: i32,
+/// }
+/// ```
+///
+/// A builder that is not ready to be built cannot be converted into the struct:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(build_method(impl_from))]
+/// struct Foo {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let _: Foo = Foo::builder().x(1).into();
+/// ```
+///
+/// `impl_from` cannot implement `From` for a type parameter of the struct:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(build_method(into = T, impl_from))]
+/// struct Foo<T: From<Foo<T>>> {
+///     x: i32,
+///     phantom: core::marker::PhantomData<T>,
+/// }
+/// ```
+///
+/// `build!` only accepts `..defaults` as the rest of the struct literal:
+///
+/// ```compile_fail
+/// use typed_builder::{TypedBuilder, build};
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     #[builder(default)]
+///     x: i32,
+/// }
+///
+/// let foo = Foo { x: 1 };
+/// build!(Foo { ..foo });
+/// ```
+///
+/// `build!` cannot skip required fields:
+///
+/// ```compile_fail
+/// use typed_builder::{TypedBuilder, build};
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo {
+///     x: i32,
+///     #[builder(default)]
+///     y: i32,
+/// }
+///
+/// build!(Foo { y: 1, ..defaults });
+/// ```
+///
+/// Fields that a preset does not set are still required:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(preset(name = local, host = "localhost"))]
+/// struct Foo {
+///     #[builder(setter(into))]
+///     host: String,
+///     port: u16,
+/// }
+///
+/// Foo::local().build();
+/// ```
+///
+/// Presets do not make their fields' setters overridable on other builders:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(preset(name = local, host = "localhost"))]
+/// struct Foo {
+///     #[builder(setter(into))]
+///     host: String,
+/// }
+///
+/// Foo::builder().host("a").host("b").build();
+/// ```
+///
+/// And a preset value can only be replaced once:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(preset(name = local, host = "localhost"))]
+/// struct Foo {
+///     #[builder(setter(into))]
+///     host: String,
+/// }
+///
+/// Foo::local().host("a").host("b").build();
+/// ```
+///
+/// In step mode, required fields cannot be set out of order:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(step)]
+/// struct Foo {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// Foo::builder().y(2).x(1).build();
+/// ```
+///
+/// In step mode, required fields cannot be `overridable`:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(step)]
+/// struct Foo {
+///     #[builder(setter(overridable))]
+///     x: i32,
+/// }
+/// ```
+///
+/// Setters that inherit the visibility of private fields cannot be called outside their module:
+///
+/// ```compile_fail
+/// mod foo {
+///     use typed_builder::TypedBuilder;
+///
+///     #[derive(TypedBuilder)]
+///     #[builder(field_defaults(setter(inherit_vis)))]
+///     pub struct Foo {
+///         pub x: i32,
+///         #[builder(default)]
+///         y: i32,
+///     }
+/// }
+///
+/// foo::Foo::builder().x(1).y(2).build();
+/// ```
+///
+/// A `setter(generic)` setter cannot change a type parameter that fields that were already set use:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo<T = ()> {
+///     #[builder(setter(generic))]
+///     x: T,
+///     #[builder(default)]
+///     y: Option<T>,
+/// }
+///
+/// Foo::builder().y(Some(())).x(1).build();
+/// ```
+///
+/// When the type parameter that a `setter(generic)` setter changes has a default, the builder
+/// method is only implemented for the default:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// struct Foo<T = ()> {
+///     #[builder(setter(generic))]
+///     x: T,
+/// }
+///
+/// Foo::<i32>::builder().x(1).build();
+/// ```
+///
+/// `const` builders cannot use setters that call traits:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(const)]
+/// struct Foo {
+///     #[builder(setter(into))]
+///     x: &'static str,
+/// }
+/// ```
+///
+/// Nor can their defaults call functions that are not `const`, since they are evaluated in the
+/// `const fn` build method:
+///
+/// ```compile_fail
+/// use typed_builder::TypedBuilder;
+///
+/// #[derive(TypedBuilder)]
+/// #[builder(const)]
+/// struct Foo {
+///     #[builder(default = String::from("foo"))]
+///     name: String,
+/// }
+/// ```
 fn _compile_fail_tests() {}
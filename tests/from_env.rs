@@ -0,0 +1,133 @@
+#![warn(clippy::pedantic)]
+#![allow(clippy::disallowed_names)]
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use typed_builder::{EnvError, TypedBuilder};
+
+// The tests run in parallel, so each test holds this lock for as long as it accesses the environment. Each test also uses
+// its own prefix, so that the variables set by one test don't affect the others.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_env() -> MutexGuard<'static, ()> {
+    ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn set_vars(_lock: &MutexGuard<'static, ()>, vars: &[(&str, &str)]) {
+    for (name, value) in vars {
+        // SAFETY: every test in this binary holds `ENV_LOCK` while it reads or writes the environment, and the caller holds
+        // it now - so no other thread accesses the environment concurrently.
+        unsafe { std::env::set_var(name, value) };
+    }
+}
+
+#[test]
+fn test_from_env() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(from_env(prefix = "TEST_FROM_ENV_"))]
+    struct Foo {
+        x: i32,
+        #[builder(default)]
+        y: i32,
+        #[builder(default = x + y)]
+        z: i32,
+        #[builder(from_env(name = "TEST_FROM_ENV_CUSTOM"))]
+        w: String,
+    }
+
+    let lock = lock_env();
+    set_vars(
+        &lock,
+        &[
+            ("TEST_FROM_ENV_X", "1"),
+            ("TEST_FROM_ENV_Y", "2"),
+            ("TEST_FROM_ENV_CUSTOM", "w"),
+        ],
+    );
+    assert_eq!(
+        Foo::from_env().unwrap(),
+        Foo {
+            x: 1,
+            y: 2,
+            z: 3,
+            w: "w".to_owned(),
+        }
+    );
+
+    set_vars(&lock, &[("TEST_FROM_ENV_Z", "5")]);
+    assert_eq!(Foo::from_env().unwrap().z, 5);
+}
+
+#[test]
+fn test_from_env_errors() {
+    #[derive(Debug, TypedBuilder)]
+    #[builder(from_env(prefix = "TEST_FROM_ENV_ERRORS_"))]
+    #[allow(dead_code)]
+    struct Foo {
+        x: i32,
+        #[builder(default)]
+        y: i32,
+        z: i32,
+        #[builder(default)]
+        w: bool,
+    }
+
+    let lock = lock_env();
+    set_vars(&lock, &[("TEST_FROM_ENV_ERRORS_Y", "two"), ("TEST_FROM_ENV_ERRORS_W", "yes")]);
+    let error = Foo::from_env().unwrap_err();
+    assert_eq!(
+        error,
+        EnvError {
+            missing: vec!["TEST_FROM_ENV_ERRORS_X", "TEST_FROM_ENV_ERRORS_Z"],
+            invalid: vec![
+                ("TEST_FROM_ENV_ERRORS_Y", "invalid digit found in string".to_owned()),
+                (
+                    "TEST_FROM_ENV_ERRORS_W",
+                    "provided string was not `true` or `false`".to_owned()
+                ),
+            ],
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "missing environment variable `TEST_FROM_ENV_ERRORS_X`; \
+         missing environment variable `TEST_FROM_ENV_ERRORS_Z`; \
+         invalid environment variable `TEST_FROM_ENV_ERRORS_Y`: invalid digit found in string; \
+         invalid environment variable `TEST_FROM_ENV_ERRORS_W`: provided string was not `true` or `false`"
+    );
+}
+
+#[test]
+fn test_from_env_setter_options() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(from_env(prefix = "TEST_FROM_ENV_SETTERS_"))]
+    struct Foo {
+        #[builder(default, setter(strip_option))]
+        x: Option<u16>,
+        #[builder(default, setter(strip_option))]
+        y: Option<u16>,
+        #[builder(setter(transform = |port: u16| format!("localhost:{port}")))]
+        z: String,
+        #[builder(via_mutators = 7)]
+        w: i32,
+    }
+
+    let lock = lock_env();
+    set_vars(
+        &lock,
+        &[
+            ("TEST_FROM_ENV_SETTERS_X", "1"),
+            ("TEST_FROM_ENV_SETTERS_Z", "80"),
+            ("TEST_FROM_ENV_SETTERS_W", "2"),
+        ],
+    );
+    assert_eq!(
+        Foo::from_env().unwrap(),
+        Foo {
+            x: Some(1),
+            y: None,
+            z: "localhost:80".to_owned(),
+            w: 7,
+        }
+    );
+}
@@ -210,3 +210,21 @@ fn test_builder_type_with_derive_attribute() {
     assert_eq!(Foo::builder().x(1), Foo::builder().x(1));
     assert_ne!(Foo::builder().x(1), Foo::builder().x(2));
 }
+
+#[test]
+fn test_patch() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(patch)]
+    struct Foo {
+        x: i32,
+        #[builder(default = *x + 1)]
+        y: i32,
+    }
+
+    let patch = FooPatch { x: Some(1), y: None }.merge(FooPatch { x: None, y: Some(3) });
+    assert_eq!(patch.build(), Ok(Foo { x: 1, y: 3 }));
+    assert_eq!(
+        FooPatch::default().merge(FooPatch { x: Some(1), y: None }).build(),
+        Ok(Foo { x: 1, y: 2 })
+    );
+}
@@ -0,0 +1,124 @@
+#![warn(clippy::pedantic)]
+#![allow(clippy::disallowed_names)]
+
+use typed_builder::TypedBuilder;
+
+#[test]
+fn test_deserialize_defaults() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(deserialize)]
+    struct Foo {
+        x: i32,
+        #[builder(default)]
+        y: i32,
+        #[builder(default = x + y)]
+        z: i32,
+        #[builder(default_code = "String::from(\"w\")")]
+        w: String,
+    }
+
+    let foo: Foo = serde_json::from_str(r#"{"x": 1, "y": 2}"#).unwrap();
+    assert_eq!(foo, Foo::builder().x(1).y(2).build());
+    assert_eq!(foo.z, 3);
+    assert_eq!(foo.w, "w");
+
+    let foo: Foo = serde_json::from_str(r#"{"x": 1, "z": 5, "w": "v"}"#).unwrap();
+    assert_eq!(
+        foo,
+        Foo {
+            x: 1,
+            y: 0,
+            z: 5,
+            w: "v".to_owned()
+        }
+    );
+}
+
+#[test]
+fn test_deserialize_missing_fields() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(deserialize)]
+    struct Foo {
+        x: i32,
+        #[builder(default)]
+        y: i32,
+        z: i32,
+    }
+
+    let error = serde_json::from_str::<Foo>(r#"{"y": 1, "z": 2}"#).unwrap_err();
+    assert_eq!(error.to_string(), "missing field `x`");
+
+    let error = serde_json::from_str::<Foo>(r#"{"y": 1}"#).unwrap_err();
+    assert_eq!(error.to_string(), "missing fields `x`, `z`");
+
+    let error = serde_json::from_str::<Foo>(r#"{"x": "1", "z": 2}"#).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid type: string \"1\", expected i32 at line 1 column 9"
+    );
+}
+
+#[test]
+fn test_deserialize_setter_options() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(deserialize)]
+    struct Foo {
+        #[builder(setter(transform = |x: u8| i32::from(x) * 2))]
+        x: i32,
+        #[builder(default, setter(strip_option, into))]
+        y: Option<String>,
+        #[builder(setter(strip_bool))]
+        z: bool,
+        #[builder(via_mutators = vec![1])]
+        w: Vec<i32>,
+        #[builder(setter(skip), default = x + 1)]
+        v: i32,
+    }
+
+    let foo: Foo = serde_json::from_str(r#"{"x": 2, "y": "y", "z": true, "w": [2]}"#).unwrap();
+    assert_eq!(
+        foo,
+        Foo {
+            x: 4,
+            y: Some("y".to_owned()),
+            z: true,
+            w: vec![1],
+            v: 5,
+        }
+    );
+
+    let foo: Foo = serde_json::from_str(r#"{"x": 2}"#).unwrap();
+    assert_eq!(
+        foo,
+        Foo {
+            x: 4,
+            y: None,
+            z: false,
+            w: vec![1],
+            v: 5,
+        }
+    );
+}
+
+#[test]
+fn test_deserialize_generics() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(deserialize)]
+    struct Foo<T, U: Default> {
+        x: T,
+        #[builder(default)]
+        y: U,
+        #[builder(default, default_where(T: Default))]
+        z: T,
+    }
+
+    let foo: Foo<i32, String> = serde_json::from_str(r#"{"x": 1}"#).unwrap();
+    assert_eq!(
+        foo,
+        Foo {
+            x: 1,
+            y: String::new(),
+            z: 0,
+        }
+    );
+}
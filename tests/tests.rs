@@ -167,6 +167,73 @@ fn test_into_with_strip_option_with_fallback() {
     assert!(Foo::builder().x_opt(Some(1)).build() == Foo { x: Some(1) });
 }
 
+#[test]
+fn test_convert() {
+    use std::time::Duration;
+
+    trait IntoDuration {
+        fn into_duration(self) -> Duration;
+    }
+
+    impl IntoDuration for Duration {
+        fn into_duration(self) -> Duration {
+            self
+        }
+    }
+
+    impl IntoDuration for u64 {
+        fn into_duration(self) -> Duration {
+            Duration::from_secs(self)
+        }
+    }
+
+    trait IntoVec<T> {
+        fn into_vec(self) -> Vec<T>;
+    }
+
+    impl<T, const N: usize> IntoVec<T> for [T; N] {
+        fn into_vec(self) -> Vec<T> {
+            self.into()
+        }
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(field_defaults(setter(convert = IntoDuration::into_duration)))]
+    struct Foo {
+        timeout: Duration,
+        #[builder(default, setter(strip_option))]
+        retry_after: Option<Duration>,
+        #[builder(setter(convert = IntoVec<u32>::into_vec))]
+        ids: Vec<u32>,
+        #[builder(setter(!convert))]
+        retries: u32,
+    }
+
+    assert_eq!(
+        Foo::builder().timeout(1).ids([1, 2]).retries(2).build(),
+        Foo {
+            timeout: Duration::from_secs(1),
+            retry_after: None,
+            ids: vec![1, 2],
+            retries: 2,
+        }
+    );
+    assert_eq!(
+        Foo::builder()
+            .timeout(Duration::from_millis(5))
+            .retry_after(3)
+            .ids([])
+            .retries(0)
+            .build(),
+        Foo {
+            timeout: Duration::from_millis(5),
+            retry_after: Some(Duration::from_secs(3)),
+            ids: vec![],
+            retries: 0,
+        }
+    );
+}
+
 #[test]
 fn test_strip_bool() {
     #[derive(PartialEq, TypedBuilder)]
@@ -192,6 +259,46 @@ fn test_strip_bool_with_fallback() {
     assert!(Foo::builder().build() == Foo { x: false });
 }
 
+#[test]
+fn test_strip_bool_with_negated() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(strip_bool(negated = no_x)))]
+        x: bool,
+        #[builder(default = true, setter(strip_bool(negated = insecure, fallback = verify_tls_bool)))]
+        verify_tls: bool,
+    }
+
+    assert_eq!(
+        Foo::builder().build(),
+        Foo {
+            x: false,
+            verify_tls: true
+        }
+    );
+    assert_eq!(
+        Foo::builder().x().insecure().build(),
+        Foo {
+            x: true,
+            verify_tls: false
+        }
+    );
+    assert_eq!(
+        Foo::builder().no_x().verify_tls().build(),
+        Foo {
+            x: false,
+            verify_tls: true
+        }
+    );
+    assert_eq!(
+        Foo::builder().verify_tls_bool(false).build(),
+        Foo {
+            x: false,
+            verify_tls: false
+        }
+    );
+}
+
 #[test]
 fn test_default() {
     #[derive(PartialEq, TypedBuilder)]
@@ -989,6 +1096,26 @@ fn test_prefix_and_suffix() {
     assert_eq!(foo, Foo { x: 1, y: 2 });
 }
 
+#[test]
+fn test_setter_name_and_aliases() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(field_defaults(setter(prefix = "with_")))]
+    struct Foo {
+        #[builder(setter(name = x_value))]
+        x: i32,
+        #[builder(default, setter(strip_option, aliases = [old_y, older_y]))]
+        y: Option<i32>,
+    }
+
+    assert_eq!(Foo::builder().x_value(1).with_y(2).build(), Foo { x: 1, y: Some(2) });
+    #[allow(deprecated)]
+    let foo = Foo::builder().x_value(1).old_y(3).build();
+    assert_eq!(foo, Foo { x: 1, y: Some(3) });
+    #[allow(deprecated)]
+    let foo = Foo::builder().older_y(4).x_value(1).build();
+    assert_eq!(foo, Foo { x: 1, y: Some(4) });
+}
+
 #[test]
 fn test_issue_118() {
     #[derive(TypedBuilder)]
@@ -1215,3 +1342,1028 @@ fn test_builder_type_with_derive_attribute() {
     assert_eq!(Foo::builder().x(1), Foo::builder().x(1));
     assert_ne!(Foo::builder().x(1), Foo::builder().x(2));
 }
+
+#[test]
+fn test_overridable_setter() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(overridable))]
+        x: i32,
+        #[builder(default, setter(overridable, strip_option(fallback = y_opt)))]
+        y: Option<i32>,
+        z: i32,
+    }
+
+    fn base() -> FooBuilder<((i32,), (Option<i32>,), ())> {
+        Foo::builder().x(1).y(2)
+    }
+
+    assert_eq!(base().z(3).build(), Foo { x: 1, y: Some(2), z: 3 });
+    assert_eq!(base().x(4).z(3).x(5).build(), Foo { x: 5, y: Some(2), z: 3 });
+    assert_eq!(base().y_opt(None).z(3).build(), Foo { x: 1, y: None, z: 3 });
+}
+
+#[test]
+fn test_overridable_field_defaults() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(field_defaults(setter(overridable)))]
+    struct Foo {
+        x: i32,
+        #[builder(default = 2)]
+        y: i32,
+    }
+
+    assert_eq!(Foo::builder().x(1).x(2).y(3).y(4).build(), Foo { x: 2, y: 4 });
+    assert_eq!(Foo::builder().x(1).build(), Foo { x: 1, y: 2 });
+}
+
+#[test]
+fn test_resettable_setter() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        #[builder(setter(resettable))]
+        x: i32,
+        #[builder(default = 2, setter(resettable))]
+        y: i32,
+        #[builder(via_mutators)]
+        z: i32,
+    }
+
+    let template = Foo::builder().x(1).y(3);
+    assert_eq!(template.clone().build(), Foo { x: 1, y: 3, z: 0 });
+    assert_eq!(template.clone().clear_y().build(), Foo { x: 1, y: 2, z: 0 });
+    assert_eq!(template.clear_x().clear_y().x(4).y(5).build(), Foo { x: 4, y: 5, z: 0 });
+    assert_eq!(Foo::builder().clear_y().x(1).build(), Foo { x: 1, y: 2, z: 0 });
+}
+
+#[test]
+fn test_getters() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(field_defaults(getters))]
+    struct Foo {
+        scheme: &'static str,
+        port: u16,
+        #[builder(via_mutators = vec![1])]
+        tags: Vec<i32>,
+    }
+
+    impl FooBuilder<((&'static str,), (), (Vec<i32>,))> {
+        fn default_port(self) -> FooBuilder<((&'static str,), (u16,), (Vec<i32>,))> {
+            let port = if *self.get_scheme() == "https" { 443 } else { 80 };
+            self.port(port)
+        }
+    }
+
+    let mut builder = Foo::builder().scheme("https");
+    builder.tags_mut().push(2);
+    assert_eq!(builder.get_tags(), &[1, 2]);
+    let builder = builder.default_port();
+    assert_eq!(*builder.get_port(), 443);
+    assert_eq!(
+        builder.build(),
+        Foo {
+            scheme: "https",
+            port: 443,
+            tags: vec![1, 2]
+        }
+    );
+}
+
+#[test]
+fn test_mutators_setting_fields() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators(
+        #[mutator(sets = [cert, key])]
+        fn tls_pair(&mut self, cert: &str, key: &str) -> (String, String) {
+            (cert.to_owned(), key.to_owned())
+        }
+        #[mutator(requires = [cert], sets = [ca])]
+        fn ca_from_cert(&mut self) -> String {
+            format!("ca-of-{}", self.cert)
+        }
+    ))]
+    struct Foo {
+        cert: String,
+        key: String,
+        #[builder(default)]
+        ca: String,
+        #[builder(via_mutators)]
+        x: i32,
+    }
+
+    assert_eq!(
+        Foo::builder().tls_pair("c", "k").build(),
+        Foo {
+            cert: "c".to_owned(),
+            key: "k".to_owned(),
+            ca: String::new(),
+            x: 0,
+        }
+    );
+    assert_eq!(
+        Foo::builder().tls_pair("c", "k").ca_from_cert().build(),
+        Foo {
+            cert: "c".to_owned(),
+            key: "k".to_owned(),
+            ca: "ca-of-c".to_owned(),
+            x: 0,
+        }
+    );
+    assert_eq!(
+        Foo::builder().cert("c".to_owned()).ca_from_cert().key("k".to_owned()).build(),
+        Foo {
+            cert: "c".to_owned(),
+            key: "k".to_owned(),
+            ca: "ca-of-c".to_owned(),
+            x: 0,
+        }
+    );
+}
+
+#[test]
+fn test_mutators_returning_values() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(mutators(
+        fn push(&mut self, value: i32) -> usize {
+            self.values.push(value);
+            self.values.len()
+        }
+        fn try_push(&mut self, value: i32) -> Result<(), String> {
+            if value < 0 {
+                return Err(format!("negative value {value}"));
+            }
+            self.values.push(value);
+            Ok(())
+        }
+        fn try_pop(&mut self) -> Result<i32, &'static str> {
+            self.values.pop().ok_or("empty")
+        }
+        #[mutator(sets = [name])]
+        fn read_name(&mut self, input: &[u8]) -> std::io::Result<String> {
+            let name = std::str::from_utf8(input).map_err(std::io::Error::other)?;
+            Ok(name.to_owned())
+        }
+    ))]
+    struct Foo {
+        #[builder(via_mutators)]
+        values: Vec<i32>,
+        name: String,
+    }
+
+    let (builder, len) = Foo::builder().push(1).0.push(2);
+    assert_eq!(len, 2);
+    let builder = builder.try_push(3).unwrap();
+    assert_eq!(builder.clone().try_push(-1).err(), Some("negative value -1".to_owned()));
+    let (builder, popped) = builder.try_pop().unwrap();
+    assert_eq!(popped, 3);
+    assert!(Foo::builder().try_pop().is_err());
+    assert!(builder.clone().read_name(b"\xff").is_err());
+    assert_eq!(
+        builder.read_name(b"foo").unwrap().build(),
+        Foo {
+            values: vec![1, 2],
+            name: "foo".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_external_mutators() {
+    #[derive(Debug, PartialEq, TypedBuilder)]
+    #[builder(external_mutators)]
+    struct Foo<T: Default + Clone> {
+        x: T,
+        #[builder(via_mutators)]
+        items: Vec<T>,
+        #[builder(default)]
+        y: T,
+    }
+
+    #[typed_builder::mutators(Foo)]
+    impl FooMutators {
+        fn add_item(&mut self, item: T) {
+            self.items.push(item);
+        }
+
+        #[mutator(requires = [x])]
+        fn add_x(&mut self) {
+            self.items.push(self.x.clone());
+        }
+
+        #[mutator(sets = [y])]
+        fn y_from_len(&mut self) -> Result<T, &'static str>
+        where
+            T: From<u8>,
+        {
+            let len = u8::try_from(self.items.len()).map_err(|_| "too many items")?;
+            Ok(T::from(len))
+        }
+    }
+
+    assert_eq!(
+        Foo::builder().add_item(1).x(2).add_x().y_from_len().unwrap().build(),
+        Foo {
+            x: 2,
+            items: vec![1, 2],
+            y: 2
+        }
+    );
+}
+
+#[test]
+fn test_external_mutators_in_other_module() {
+    mod outer {
+        pub mod config {
+            use typed_builder::TypedBuilder;
+
+            #[derive(Debug, PartialEq, TypedBuilder)]
+            #[builder(external_mutators)]
+            pub struct Foo {
+                #[builder(via_mutators)]
+                pub items: Vec<i32>,
+            }
+        }
+
+        pub mod mutators {
+            #[typed_builder::mutators(super::config::Foo)]
+            impl FooMutators {
+                pub fn add_item(&mut self, item: i32) {
+                    self.items.push(item);
+                }
+            }
+        }
+    }
+
+    assert_eq!(
+        outer::config::Foo::builder().add_item(1).add_item(2).build(),
+        outer::config::Foo { items: vec![1, 2] }
+    );
+}
+
+#[test]
+fn test_presets() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(
+        preset(name = local, host = "localhost", port = 8080),
+        preset(name = production, host = "example.com", tls = true, r#name = "prod"),
+    )]
+    struct Server {
+        #[builder(setter(into))]
+        host: String,
+        #[builder(default = 80)]
+        port: u16,
+        #[builder(default, setter(strip_option))]
+        tls: Option<bool>,
+        #[builder(default, setter(into))]
+        name: String,
+        workers: usize,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(
+        builder_type(debug),
+        preset(name = preset, x = 1, z = 3),
+        mutators(
+            #[mutator(requires = [x])]
+            fn double_x(&mut self) {
+                self.x *= 2;
+            }
+        )
+    )]
+    struct Foo {
+        #[builder(getters)]
+        x: i32,
+        y: i32,
+        #[builder(default = x + y, default_where())]
+        z: i32,
+    }
+
+    assert_eq!(
+        Server::local().workers(1).build(),
+        Server {
+            host: "localhost".to_owned(),
+            port: 8080,
+            tls: None,
+            name: String::new(),
+            workers: 1,
+        }
+    );
+    assert_eq!(
+        Server::local().port(9090).host("127.0.0.1").workers(2).build(),
+        Server {
+            host: "127.0.0.1".to_owned(),
+            port: 9090,
+            tls: None,
+            name: String::new(),
+            workers: 2,
+        }
+    );
+    assert_eq!(
+        Server::production().workers(4).build(),
+        Server {
+            host: "example.com".to_owned(),
+            port: 80,
+            tls: Some(true),
+            name: "prod".to_owned(),
+            workers: 4,
+        }
+    );
+
+    let builder = Foo::preset();
+    assert_eq!(*builder.get_x(), 1);
+    assert_eq!(format!("{builder:?}"), "FooBuilder { x: 1, y: <unset>, z: 3 }");
+    assert_eq!(builder.clone().y(2).build(), Foo { x: 1, y: 2, z: 3 });
+    assert_eq!(builder.x(5).double_x().y(2).build(), Foo { x: 10, y: 2, z: 3 });
+    assert_eq!(Foo::preset().double_x().z(4).y(2).build(), Foo { x: 2, y: 2, z: 4 });
+    assert_eq!(Foo::builder().x(1).y(2).build(), Foo { x: 1, y: 2, z: 3 });
+}
+
+#[test]
+fn test_builder_method_args() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(builder_method(args = [url, port]))]
+    struct Client {
+        #[builder(setter(into))]
+        url: String,
+        #[builder(setter(transform = |port: u16| i32::from(port)))]
+        port: i32,
+        #[builder(default = 30)]
+        timeout: u32,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(builder_method(name = new, args = [value]), preset(name = zero, value = 0))]
+    struct Foo {
+        #[builder(setter(strip_option))]
+        value: Option<i32>,
+        #[builder(default)]
+        other: i32,
+    }
+
+    // The setters' parameters are renamed per field, so the transforms can share parameter names.
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(builder_method(args = [width, height]))]
+    struct Size {
+        #[builder(setter(transform = |value: u16| u32::from(value)))]
+        width: u32,
+        #[builder(setter(transform = |value: u16, (scale, offset): (u16, u16)| u32::from(value * scale + offset)))]
+        height: u32,
+    }
+
+    assert_eq!(
+        Client::builder("db", 5432).build(),
+        Client {
+            url: "db".to_owned(),
+            port: 5432,
+            timeout: 30,
+        }
+    );
+    assert_eq!(
+        Client::builder("db", 5432).timeout(5).build(),
+        Client {
+            url: "db".to_owned(),
+            port: 5432,
+            timeout: 5,
+        }
+    );
+
+    assert_eq!(
+        Foo::new(1).other(2).build(),
+        Foo {
+            value: Some(1),
+            other: 2
+        }
+    );
+    assert_eq!(
+        Foo::zero().value(3).build(),
+        Foo {
+            value: Some(3),
+            other: 0
+        }
+    );
+
+    assert_eq!(Size::builder(3, 4, (2, 1)).build(), Size { width: 3, height: 9 });
+}
+
+#[test]
+fn test_constructor() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(new)]
+    struct Foo {
+        #[builder(setter(into))]
+        x: String,
+        #[builder(default = x.len())]
+        y: usize,
+        #[builder(setter(strip_option))]
+        z: Option<i32>,
+        #[builder(via_mutators = 4)]
+        w: i32,
+        #[builder(setter(skip), default = y + 1)]
+        v: usize,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(new(name = with_value, vis = "pub(crate)"))]
+    struct Bar<T> {
+        value: T,
+        #[builder(default, default_where(T: Default))]
+        other: T,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(new)]
+    struct Point {
+        #[builder(setter(transform = |value: i32| f64::from(value)))]
+        x: f64,
+        #[builder(setter(transform = |value: i32| f64::from(value)))]
+        y: f64,
+    }
+
+    assert_eq!(
+        Foo::new("abc", 2),
+        Foo {
+            x: "abc".to_owned(),
+            y: 3,
+            z: Some(2),
+            w: 4,
+            v: 4,
+        }
+    );
+    assert_eq!(Foo::new("abc", 2), Foo::builder().x("abc").z(2).build());
+
+    assert_eq!(Bar::with_value(1), Bar { value: 1, other: 0 });
+
+    assert_eq!(Point::new(1, 2), Point { x: 1.0, y: 2.0 });
+}
+
+#[test]
+fn test_build_macro() {
+    use typed_builder::build;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Inner {
+        #[builder(default, setter(strip_option))]
+        x: Option<i32>,
+        #[builder(setter(transform = |a: i32| a * 2))]
+        y: i32,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Outer<T> {
+        #[builder(setter(into))]
+        name: String,
+        #[builder(default = name.len())]
+        len: usize,
+        inner: Inner,
+        value: T,
+    }
+
+    let value = 4_u8;
+    assert_eq!(
+        build!(Outer::<u8> {
+            name: "outer",
+            inner: build!(Inner { x: 1, y: 3 }),
+            value,
+            ..defaults
+        }),
+        Outer {
+            name: "outer".to_owned(),
+            len: 5,
+            inner: Inner { x: Some(1), y: 6 },
+            value: 4,
+        }
+    );
+    assert_eq!(build!(Inner { y: 1 }), Inner { x: None, y: 2 });
+}
+
+#[test]
+fn test_from_complete_builder() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(impl_from))]
+    struct RetryPolicy {
+        attempts: u32,
+        #[builder(default = 100)]
+        delay_ms: u32,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Client {
+        #[builder(setter(into))]
+        retry: RetryPolicy,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(build_method(into = Option<Generic<T>>, impl_from))]
+    struct Generic<T> {
+        value: T,
+    }
+
+    // Without `impl_from`, the builder can have `From` impls of its own.
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Plain {
+        value: i32,
+    }
+
+    impl From<PlainBuilder<((i32,),)>> for RetryPolicy {
+        fn from(builder: PlainBuilder<((i32,),)>) -> Self {
+            RetryPolicy::builder().attempts(builder.build().value.unsigned_abs()).build()
+        }
+    }
+
+    // Nor does it conflict with converting into a type parameter.
+    #[derive(TypedBuilder)]
+    #[builder(build_method(into = T))]
+    struct Wrapper<T: From<Wrapper<T>>> {
+        value: i32,
+        #[builder(default)]
+        phantom: core::marker::PhantomData<T>,
+    }
+
+    #[derive(PartialEq, Debug)]
+    struct Doubled(i32);
+
+    impl From<Wrapper<Doubled>> for Doubled {
+        fn from(wrapper: Wrapper<Doubled>) -> Self {
+            Doubled(wrapper.value * 2)
+        }
+    }
+
+    fn takes_policy(policy: impl Into<RetryPolicy>) -> RetryPolicy {
+        policy.into()
+    }
+
+    assert_eq!(
+        takes_policy(RetryPolicy::builder().attempts(3)),
+        RetryPolicy {
+            attempts: 3,
+            delay_ms: 100
+        }
+    );
+    assert_eq!(
+        takes_policy(RetryPolicy::builder().attempts(3).delay_ms(5)),
+        RetryPolicy {
+            attempts: 3,
+            delay_ms: 5
+        }
+    );
+    assert_eq!(
+        Client::builder().retry(RetryPolicy::builder().attempts(3)).build(),
+        Client {
+            retry: RetryPolicy {
+                attempts: 3,
+                delay_ms: 100
+            }
+        }
+    );
+
+    let generic: Option<Generic<i32>> = Generic::builder().value(1).into();
+    assert_eq!(generic, Some(Generic { value: 1 }));
+
+    assert_eq!(
+        RetryPolicy::from(Plain::builder().value(-2)),
+        RetryPolicy {
+            attempts: 2,
+            delay_ms: 100
+        }
+    );
+    assert_eq!(Wrapper::<Doubled>::builder().value(2).build(), Doubled(4));
+}
+
+#[test]
+fn test_builder_debug() {
+    struct NoDebug;
+
+    #[derive(TypedBuilder)]
+    #[builder(builder_type(debug))]
+    #[allow(dead_code)]
+    struct Foo<T> {
+        #[builder(setter(into))]
+        host: String,
+        port: u16,
+        #[builder(default)]
+        timeout: u32,
+        // `Debug` is only required for the fields that are set
+        #[builder(default)]
+        no_debug: Option<NoDebug>,
+        #[builder(via_mutators)]
+        r#type: Vec<T>,
+    }
+
+    assert_eq!(
+        format!("{:?}", Foo::<i32>::builder().host("x")),
+        r#"FooBuilder { host: "x", port: <unset>, timeout: <default>, no_debug: <default>, type: [] }"#
+    );
+    assert_eq!(
+        format!("{:?}", Foo::<i32>::builder().port(80).timeout(5)),
+        "FooBuilder { host: <unset>, port: 80, timeout: 5, no_debug: <default>, type: [] }"
+    );
+}
+
+#[test]
+fn test_step() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(step, preset(name = with_z, z = 3))]
+    struct Bar {
+        x: i32,
+        y: i32,
+        #[builder(default, setter(resettable))]
+        z: i32,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(step, builder_method(args = [x]))]
+    struct Foo<T> {
+        x: i32,
+        #[builder(default)]
+        y: i32,
+        #[builder(setter(into))]
+        z: T,
+        w: i32,
+    }
+
+    // Optional fields can be set in any state
+    assert_eq!(
+        Foo::builder(1).y(2).z("z").w(4).build(),
+        Foo {
+            x: 1,
+            y: 2,
+            z: "z",
+            w: 4
+        }
+    );
+    assert_eq!(Foo::<&str>::builder(1).z("z").y(3).w(4).y(2).build().y, 2);
+
+    // Each state has a concrete type
+    let builder: FooBuilder<&str, ((i32,), Option<i32>, (), ())> = Foo::builder(1);
+    let builder: FooBuilder<&str, ((i32,), Option<i32>, (&str,), ())> = builder.y(2).z("z");
+    assert_eq!(
+        builder.w(4).build(),
+        Foo {
+            x: 1,
+            y: 2,
+            z: "z",
+            w: 4
+        }
+    );
+
+    assert_eq!(Bar::builder().z(5).x(1).y(2).clear_z().build(), Bar { x: 1, y: 2, z: 0 });
+    assert_eq!(Bar::with_z().x(1).y(2).build(), Bar { x: 1, y: 2, z: 3 });
+    assert_eq!(Bar::with_z().x(1).z(4).y(2).build(), Bar { x: 1, y: 2, z: 4 });
+}
+
+#[test]
+fn test_setter_vis() {
+    mod foo {
+        use typed_builder::TypedBuilder;
+
+        #[derive(PartialEq, Debug, TypedBuilder)]
+        #[builder(field_defaults(setter(inherit_vis)))]
+        pub struct Foo {
+            pub x: i32,
+            #[builder(default, setter(resettable))]
+            pub(super) y: i32,
+            #[builder(default, setter(!inherit_vis))]
+            pub z: i32,
+            #[builder(default = 1, mutators(
+                fn double_w(self) {
+                    self.w *= 2;
+                }
+            ))]
+            w: i32,
+        }
+
+        impl FooBuilder<((i32,), (), (), ())> {
+            pub fn with_w(self, w: i32) -> FooBuilder<((i32,), (), (), (i32,))> {
+                self.w(w).double_w()
+            }
+        }
+
+        impl Foo {
+            pub fn w(&self) -> i32 {
+                self.w
+            }
+        }
+    }
+
+    let foo = foo::Foo::builder().x(1).with_w(2).y(3).clear_y().z(4).build();
+    assert_eq!((foo.x, foo.y, foo.z, foo.w()), (1, 0, 4, 4));
+}
+
+#[test]
+fn test_generic_setter() {
+    #[derive(PartialEq, Debug, Default)]
+    struct NoAuth;
+
+    #[derive(PartialEq, Debug, Default)]
+    struct Bearer(&'static str);
+
+    trait Auth {}
+    impl Auth for NoAuth {}
+    impl Auth for Bearer {}
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Client<T: Auth = NoAuth, U = ()> {
+        #[builder(default, default_where(T: Default), setter(generic))]
+        auth: T,
+        #[builder(default)]
+        fallback: Option<T>,
+        #[builder(setter(into))]
+        url: String,
+        #[builder(setter(generic))]
+        user: U,
+    }
+
+    assert_eq!(
+        Client::builder().url("x").user(()).build(),
+        Client {
+            auth: NoAuth,
+            fallback: None,
+            url: "x".to_owned(),
+            user: ()
+        }
+    );
+    assert_eq!(
+        Client::builder().url("x").auth(Bearer("token")).user(1).build(),
+        Client {
+            auth: Bearer("token"),
+            fallback: None,
+            url: "x".to_owned(),
+            user: 1
+        }
+    );
+    assert_eq!(
+        Client::builder()
+            .user("user")
+            .auth(Bearer("token"))
+            .fallback(Some(Bearer("other")))
+            .url("x")
+            .build()
+            .fallback,
+        Some(Bearer("other"))
+    );
+
+    // The builder method is implemented for the defaults of the type parameters that the setters change.
+    assert_eq!(
+        Client::<NoAuth, ()>::builder().url("x").user(1).build(),
+        Client {
+            auth: NoAuth,
+            fallback: None,
+            url: "x".to_owned(),
+            user: 1
+        }
+    );
+}
+
+#[test]
+fn test_in_place_storage() {
+    struct Uncloneable;
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(
+        builder_type(storage = "in_place", debug),
+        preset(name = one, y = 1),
+        mutators(
+            #[mutator(requires = [x])]
+            fn double_x(&mut self) {
+                self.x *= 2;
+            }
+        )
+    )]
+    struct Foo<T> {
+        #[builder(via_mutators = 3)]
+        x: i32,
+        #[builder(getters, setter(resettable))]
+        y: i32,
+        #[builder(default = Some(y * 10), setter(strip_option))]
+        z: Option<i32>,
+        #[builder(default, setter(into))]
+        w: Vec<T>,
+        #[builder(setter(skip), default = y + 1)]
+        v: i32,
+    }
+
+    #[derive(TypedBuilder)]
+    #[builder(builder_type(storage = "in_place"))]
+    #[allow(dead_code)]
+    struct Bar {
+        x: i32,
+        #[builder(default)]
+        uncloneable: Option<Uncloneable>,
+        #[builder(default, setter(overridable))]
+        n: i32,
+    }
+
+    let builder = Foo::<u8>::builder().y(1).double_x();
+    assert_eq!(*builder.get_y(), 1);
+    assert_eq!(
+        format!("{builder:?}"),
+        "FooBuilder { x: 6, y: 1, z: <default>, w: <default> }"
+    );
+    assert_eq!(
+        builder.clone().build(),
+        Foo {
+            x: 6,
+            y: 1,
+            z: Some(10),
+            w: vec![],
+            v: 2
+        }
+    );
+    assert_eq!(
+        builder.clear_y().z(5).y(2).w([1]).build(),
+        Foo {
+            x: 6,
+            y: 2,
+            z: Some(5),
+            w: vec![1],
+            v: 3
+        }
+    );
+    assert_eq!(Foo::<u8>::one().y(2).build().y, 2);
+
+    // Like with tuple storage, cloning only requires `Clone` for the set fields
+    let _ = Bar::builder().x(1).clone().build();
+    assert_eq!(Bar::builder().n(1).x(1).n(2).build().n, 2);
+}
+
+#[test]
+fn test_cfg_fields() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    struct Foo {
+        x: i32,
+        #[cfg(not(test))]
+        disabled: i32,
+        #[cfg(test)]
+        #[builder(default = x + 1)]
+        enabled: i32,
+        #[cfg_attr(test, builder(default = enabled * 10))]
+        y: i32,
+        #[cfg_attr(not(test), builder(default))]
+        z: i32,
+    }
+
+    assert_eq!(
+        Foo::builder().x(1).z(3).build(),
+        Foo {
+            x: 1,
+            enabled: 2,
+            y: 20,
+            z: 3
+        }
+    );
+}
+
+#[test]
+fn test_const_builder() {
+    #[derive(PartialEq, Debug)]
+    struct Pin(u8);
+
+    #[derive(PartialEq, Debug, Clone, TypedBuilder)]
+    #[builder(const, builder_method(args = [address]))]
+    struct Register<T: Copy> {
+        address: u32,
+        #[builder(default = 4)]
+        width: u8,
+        #[builder(default = None, setter(strip_option))]
+        tag: Option<T>,
+        #[builder(setter(skip), default = *width * 8)]
+        bits: u8,
+        #[builder(default = *width + 1)]
+        stride: u8,
+        #[builder(setter(strip_bool))]
+        read_only: bool,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(const)]
+    struct Label {
+        // Fields with drop glue can be used as long as the builder does not drop them
+        #[builder(default = String::new())]
+        text: String,
+        #[builder(default = text.len())]
+        len: usize,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(const, step)]
+    struct Wiring {
+        // Fields do not need to be `Copy`
+        input: Pin,
+        output: Pin,
+    }
+
+    const fn register(address: u32) -> Register<u8> {
+        Register::builder(address).read_only().build()
+    }
+
+    static REGISTERS: [Register<u8>; 2] = [register(0x10), Register::builder(0x14).tag(1).width(2).stride(4).build()];
+    static EMPTY_LABEL: Label = Label::builder().build();
+    const WIRING: Wiring = Wiring::builder().input(Pin(1)).output(Pin(2)).build();
+
+    assert_eq!(
+        REGISTERS,
+        [
+            Register {
+                address: 0x10,
+                width: 4,
+                tag: None,
+                bits: 32,
+                stride: 5,
+                read_only: true,
+            },
+            Register {
+                address: 0x14,
+                width: 2,
+                tag: Some(1),
+                bits: 16,
+                stride: 4,
+                read_only: false,
+            },
+        ]
+    );
+    assert_eq!(
+        EMPTY_LABEL,
+        Label {
+            text: String::new(),
+            len: 0
+        }
+    );
+    assert_eq!(
+        Label::builder().text("pin".to_owned()).build(),
+        Label {
+            text: "pin".to_owned(),
+            len: 3
+        }
+    );
+    assert_eq!(
+        WIRING,
+        Wiring {
+            input: Pin(1),
+            output: Pin(2)
+        }
+    );
+
+    // The builders still work at runtime, and cloning keeps the defaults
+    assert_eq!(Register::<u8>::builder(1).clone().build().width, 4);
+}
+
+#[test]
+fn test_patch() {
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(patch(attributes(#[derive(Clone, Debug, PartialEq)])))]
+    struct Config<T> {
+        #[builder(setter(into))]
+        host: String,
+        port: u16,
+        #[builder(default = port + 1)]
+        admin_port: u16,
+        #[builder(default, setter(strip_option))]
+        tag: Option<T>,
+        #[builder(setter(skip), default = format!("{host}:{port}"))]
+        address: String,
+    }
+
+    #[derive(PartialEq, Debug, TypedBuilder)]
+    #[builder(patch(name = Layer))]
+    struct Flags {
+        #[builder(default)]
+        verbose: bool,
+    }
+
+    let defaults = ConfigPatch {
+        port: Some(80),
+        ..Default::default()
+    };
+    let file = ConfigPatch {
+        host: Some("example.com".to_owned()),
+        port: Some(8080),
+        ..Default::default()
+    };
+    // The patch fields ignore the setter options, so `strip_option` fields are `Option`s of `Option`s
+    let cli = ConfigPatch {
+        tag: Some(Some('x')),
+        ..Default::default()
+    };
+
+    // Later layers override earlier ones, and the defaults see the merged values
+    assert_eq!(
+        defaults.clone().merge(file).merge(cli).build(),
+        Ok(Config {
+            host: "example.com".to_owned(),
+            port: 8080,
+            admin_port: 8081,
+            tag: Some('x'),
+            address: "example.com:8080".to_owned(),
+        })
+    );
+
+    // All the missing required fields are reported
+    let error = ConfigPatch::<()>::default().build().unwrap_err();
+    assert_eq!(error.missing().collect::<Vec<_>>(), ["host", "port"]);
+    assert_eq!(error.to_string(), "missing fields `host`, `port`");
+    assert_eq!(defaults.build().unwrap_err().to_string(), "missing field `host`");
+
+    // The error type is the same for all patches
+    let flags: Result<Flags, typed_builder::MissingFields> = Layer::default().build();
+    assert_eq!(flags, Ok(Flags { verbose: false }));
+}
@@ -1,9 +1,10 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, quote};
 use syn::parse::Error;
 
 use crate::field_info::FieldBuilderAttr;
 use crate::mutator::Mutator;
+use crate::preset::Preset;
 use crate::util::{ApplyMeta, AttrArg, path_to_single_string};
 
 #[derive(Debug, Default, Clone)]
@@ -56,6 +57,20 @@ impl CommonDeclarationSettings {
 pub struct BuilderTypeSettings {
     pub common: CommonDeclarationSettings,
     pub attributes: Vec<syn::Attribute>,
+    /// Implement `Debug` for the builder, showing which fields are set
+    pub debug: Option<Span>,
+    pub storage: BuilderStorage,
+}
+
+/// How the builder keeps the values of the fields.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BuilderStorage {
+    /// A tuple with a `()` or `(T,)` slot per field, which is also the type that tracks which fields are set.
+    #[default]
+    Tuple,
+    /// A struct with an `Option<T>` slot per field, which the setters write in place. Which fields are set is only
+    /// tracked in the builder's type.
+    InPlace,
 }
 
 struct InlineAttributes(Vec<syn::Attribute>);
@@ -74,6 +89,49 @@ impl ApplyMeta for BuilderTypeSettings {
                 self.attributes = attributes;
                 Ok(())
             }
+            "debug" => expr.apply_flag_to_field(&mut self.debug, "given a Debug implementation"),
+            "storage" => {
+                let storage = expr.key_value()?.parse_value::<syn::LitStr>()?;
+                self.storage = match storage.value().as_str() {
+                    "tuple" => BuilderStorage::Tuple,
+                    "in_place" => BuilderStorage::InPlace,
+                    _ => return Err(Error::new_spanned(storage, "expected \"tuple\" or \"in_place\"")),
+                };
+                Ok(())
+            }
+            _ => self.common.apply_meta(expr),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BuilderMethodSettings {
+    pub common: CommonDeclarationSettings,
+
+    /// Fields that the builder method takes as positional arguments, so that the builder starts with them set.
+    pub args: Vec<syn::Ident>,
+}
+
+impl ApplyMeta for BuilderMethodSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "args" => match expr.key_value()?.parse_value()? {
+                syn::Expr::Array(syn::ExprArray { elems, .. }) => {
+                    for elem in elems {
+                        match elem {
+                            syn::Expr::Path(path) if path.path.get_ident().is_some() => {
+                                self.args.push(path.path.get_ident().cloned().expect("should be ident"));
+                            }
+                            elem => return Err(Error::new_spanned(elem, "Expected field name")),
+                        }
+                    }
+                    Ok(())
+                }
+                expr => Err(Error::new_spanned(
+                    expr,
+                    "Only list of field names [field1, field2, …] supported",
+                )),
+            },
             _ => self.common.apply_meta(expr),
         }
     }
@@ -97,6 +155,9 @@ pub struct BuildMethodSettings {
 
     /// Whether to convert the built type into another while finishing the build.
     pub into: IntoSetting,
+
+    /// Implement `From` of the builder that is ready to be built for the type the build method returns.
+    pub impl_from: Option<Span>,
 }
 
 impl ApplyMeta for BuildMethodSettings {
@@ -114,18 +175,59 @@ impl ApplyMeta for BuildMethodSettings {
                 }
                 _ => Err(expr.incorrect_type()),
             },
+            "impl_from" => expr.apply_flag_to_field(&mut self.impl_from, "impl_from"),
+            _ => self.common.apply_meta(expr),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PatchTypeSettings {
+    pub common: CommonDeclarationSettings,
+    pub attributes: Vec<syn::Attribute>,
+}
+
+impl ApplyMeta for PatchTypeSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "attributes" => {
+                let InlineAttributes(attributes) = syn::parse2(expr.sub_attr()?.args)?;
+                self.attributes = attributes;
+                Ok(())
+            }
             _ => self.common.apply_meta(expr),
         }
     }
 }
 
+#[derive(Debug, Default, Clone)]
+pub struct FromEnvSettings {
+    /// Prepended to the uppercased names of the fields to get the names of the environment variables
+    pub prefix: String,
+}
+
+impl ApplyMeta for FromEnvSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "prefix" => {
+                self.prefix = expr.key_value()?.parse_value::<syn::LitStr>()?.value();
+                Ok(())
+            }
+            _ => Err(Error::new_spanned(
+                expr.name(),
+                format!("Unknown parameter {:?}", expr.name().to_string()),
+            )),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct TypeBuilderAttr<'a> {
     /// Whether to show docs for the `TypeBuilder` type (rather than hiding them).
     pub doc: bool,
 
     /// Customize builder method, ex. visibility, name
-    pub builder_method: CommonDeclarationSettings,
+    pub builder_method: BuilderMethodSettings,
 
     /// Customize builder type, ex. visibility, name
     pub builder_type: BuilderTypeSettings,
@@ -139,6 +241,30 @@ pub struct TypeBuilderAttr<'a> {
 
     /// Functions that are able to mutate fields in the builder that are already set
     pub mutators: Vec<Mutator>,
+
+    /// Allow defining mutators outside the derive, with `#[typed_builder::mutators]`
+    pub external_mutators: Option<Span>,
+
+    /// Implement serde's `Deserialize` for the struct, resolving the defaults like the builder does
+    pub deserialize: Option<Span>,
+
+    /// Generate a `from_env()` method that reads the fields from environment variables
+    pub from_env: Option<FromEnvSettings>,
+
+    /// Named sets of field values, each generating a method that creates a builder with these fields set
+    pub presets: Vec<Preset>,
+
+    /// Generate a constructor that takes the required fields, ex. visibility, name
+    pub constructor: Option<CommonDeclarationSettings>,
+
+    /// Require setting the required fields in declaration order
+    pub step: Option<Span>,
+
+    /// Make the builder method, the setters and the build method `const fn`s
+    pub const_builder: Option<Span>,
+
+    /// Generate a companion type with every field optional, for merging layers of configuration, ex. visibility, name
+    pub patch: Option<PatchTypeSettings>,
 }
 
 impl Default for TypeBuilderAttr<'_> {
@@ -151,6 +277,14 @@ impl Default for TypeBuilderAttr<'_> {
             field_defaults: Default::default(),
             crate_module_path: syn::parse_quote!(::typed_builder),
             mutators: Default::default(),
+            external_mutators: None,
+            deserialize: None,
+            from_env: None,
+            presets: Default::default(),
+            constructor: None,
+            step: None,
+            const_builder: None,
+            patch: None,
         }
     }
 }
@@ -211,6 +345,71 @@ impl ApplyMeta for TypeBuilderAttr<'_> {
                 self.mutators.extend(expr.sub_attr()?.undelimited()?);
                 Ok(())
             }
+            "deserialize" => {
+                let name = expr.flag()?;
+                if cfg!(not(feature = "serde")) {
+                    return Err(Error::new_spanned(
+                        name,
+                        "`deserialize` requires the `serde` feature of typed-builder",
+                    ));
+                }
+                self.deserialize = Some(name.span());
+                Ok(())
+            }
+            "from_env" => {
+                let name = expr.name().clone();
+                let mut from_env = FromEnvSettings::default();
+                match expr {
+                    AttrArg::Flag(_) => {}
+                    AttrArg::Sub(sub) => from_env.apply_sub_attr(sub)?,
+                    _ => return Err(expr.incorrect_type()),
+                }
+                if cfg!(not(feature = "from_env")) {
+                    return Err(Error::new_spanned(
+                        name,
+                        "`from_env` requires the `from_env` feature of typed-builder",
+                    ));
+                }
+                self.from_env = Some(from_env);
+                Ok(())
+            }
+            "new" => {
+                let constructor = self.constructor.get_or_insert_with(Default::default);
+                match expr {
+                    AttrArg::Flag(_) => Ok(()),
+                    AttrArg::Sub(sub) => constructor.apply_sub_attr(sub),
+                    AttrArg::Not { .. } => {
+                        self.constructor = None;
+                        Ok(())
+                    }
+                    _ => Err(expr.incorrect_type()),
+                }
+            }
+            "external_mutators" => expr.apply_flag_to_field(&mut self.external_mutators, "given external mutators"),
+            "step" => expr.apply_flag_to_field(&mut self.step, "set to step mode"),
+            "const" => expr.apply_flag_to_field(&mut self.const_builder, "const"),
+            "patch" => {
+                let patch = self.patch.get_or_insert_with(Default::default);
+                match expr {
+                    AttrArg::Flag(_) => Ok(()),
+                    AttrArg::Sub(sub) => patch.apply_sub_attr(sub),
+                    AttrArg::Not { .. } => {
+                        self.patch = None;
+                        Ok(())
+                    }
+                    _ => Err(expr.incorrect_type()),
+                }
+            }
+            "preset" => {
+                let sub_attr = expr.sub_attr()?;
+                let mut preset = Preset::new(sub_attr.name.span());
+                preset.apply_sub_attr(sub_attr)?;
+                if preset.name.is_none() {
+                    return Err(Error::new(preset.span, "preset must have a `name`"));
+                }
+                self.presets.push(preset);
+                Ok(())
+            }
             "field_defaults" => self.field_defaults.apply_sub_attr(expr.sub_attr()?),
             "builder_method" => self.builder_method.apply_sub_attr(expr.sub_attr()?),
             "builder_type" => self.builder_type.apply_sub_attr(expr.sub_attr()?),
@@ -0,0 +1,69 @@
+//! Measures how much code the derive generates for wide structs, which dominates `cargo check` time for them, and
+//! compares it with the code generated by typed-builder 0.23.2. Run with
+//! `cargo test -p typed-builder-macro expansion_size -- --nocapture` to see the numbers.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote};
+
+fn count_tokens(tokens: TokenStream) -> usize {
+    tokens
+        .into_iter()
+        .map(|tree| match tree {
+            TokenTree::Group(group) => 1 + count_tokens(group.stream()),
+            _ => 1,
+        })
+        .sum()
+}
+
+/// The number of tokens generated for a struct with `width` fields, each declared with `field_attr`.
+fn expansion_size(width: usize, field_attr: TokenStream) -> usize {
+    let fields = (0..width).map(|i| {
+        let name = format_ident!("field{}", i);
+        quote!(#field_attr #name: u32)
+    });
+    let ast = syn::parse2(quote! {
+        struct Foo<T> {
+            #(#fields,)*
+            #[builder(default)]
+            t: Option<T>,
+        }
+    })
+    .unwrap();
+    count_tokens(crate::impl_my_derive(&ast).unwrap())
+}
+
+#[test]
+fn expansion_size_of_wide_structs() {
+    // The number of tokens typed-builder 0.23.2 generates for 25, 50 and 100 fields, and how much smaller (in percents)
+    // the expansion should be now. Only the build method's default resolution was shrunk, so structs without defaults
+    // are not expected to get smaller.
+    let configurations = [
+        ("required", quote!(), [21269, 66869, 233069], 0),
+        ("default", quote!(#[builder(default)]), [28644, 92244, 326319], 3),
+        ("default = expr", quote!(#[builder(default = 1)]), [28344, 91644, 325119], 3),
+        (
+            "default_where",
+            quote!(#[builder(default, default_where(T: Clone))]),
+            [28744, 92444, 326719],
+            3,
+        ),
+    ];
+    for (description, field_attr, sizes_in_0_23_2, reduction) in configurations {
+        let sizes = [25, 50, 100].map(|width| expansion_size(width, field_attr.clone()));
+        println!("{description:>16}: {sizes:?} tokens for 25, 50 and 100 fields, was {sizes_in_0_23_2:?}");
+        for (size, size_in_0_23_2) in sizes.into_iter().zip(sizes_in_0_23_2) {
+            assert!(
+                size * 100 <= size_in_0_23_2 * (100 - reduction),
+                "{description}: {sizes:?} is not {reduction}% smaller than {sizes_in_0_23_2:?}",
+            );
+        }
+        // The setters of each field, and the errors for setting it twice, still spell out the types of all the other
+        // fields - so doubling the fields multiplies the expansion by about 3.5. Make sure it does not grow any faster.
+        for pair in sizes.windows(2) {
+            assert!(
+                pair[1] * 10 <= pair[0] * 36,
+                "{description}: {sizes:?} grows faster than 3.6x per doubling"
+            );
+        }
+    }
+}
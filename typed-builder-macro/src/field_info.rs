@@ -1,7 +1,7 @@
 use std::ops::Deref;
 
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, quote_spanned};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
 use syn::parse::Parser;
 use syn::punctuated::Punctuated;
 use syn::{Expr, ExprBlock};
@@ -9,7 +9,9 @@ use syn::{parse::Error, spanned::Spanned};
 
 use crate::mutator::Mutator;
 use crate::struct_info::StructInfo;
-use crate::util::{ApplyMeta, AttrArg, expr_to_lit_string, ident_to_type, path_to_single_string, strip_raw_ident_prefix};
+use crate::util::{
+    ApplyMeta, AttrArg, expr_to_lit_string, ident_to_type, path_to_single_string, public_visibility, strip_raw_ident_prefix,
+};
 
 #[derive(Debug)]
 pub struct FieldInfo<'a> {
@@ -17,7 +19,10 @@ pub struct FieldInfo<'a> {
     pub name: &'a syn::Ident,
     pub generic_ident: syn::Ident,
     pub ty: &'a syn::Type,
+    pub vis: &'a syn::Visibility,
     pub builder_attr: FieldBuilderAttr<'a>,
+    /// Whether a preset sets the field - in which case the builders created by the preset hold it in a `Preset` slot
+    pub set_by_preset: bool,
 }
 
 impl<'a> FieldInfo<'a> {
@@ -28,7 +33,9 @@ impl<'a> FieldInfo<'a> {
                 name,
                 generic_ident: syn::Ident::new(&format!("__{}", strip_raw_ident_prefix(name.to_string())), Span::call_site()),
                 ty: &field.ty,
+                vis: &field.vis,
                 builder_attr: field_defaults.with(name, &field.attrs)?,
+                set_by_preset: false,
             }
             .post_process()
         } else {
@@ -44,6 +51,34 @@ impl<'a> FieldInfo<'a> {
         ident_to_type(self.generic_ident.clone())
     }
 
+    /// The struct's type parameter that a `setter(generic)` setter changes - the field's type.
+    pub fn generic_setter_param(&self) -> Option<&Ident> {
+        self.builder_attr.setter.generic?;
+        match self.ty {
+            syn::Type::Path(syn::TypePath { qself: None, path }) => path.get_ident(),
+            _ => None,
+        }
+    }
+
+    /// The visibility of the field's setters, if configured with `setter(vis = "...")` or `setter(inherit_vis)`.
+    pub fn configured_setter_vis(&self) -> Option<syn::Visibility> {
+        let setter = &self.builder_attr.setter;
+        setter
+            .vis
+            .clone()
+            .or_else(|| setter.inherit_vis.is_some().then(|| self.vis.clone()))
+    }
+
+    /// The visibility of the field's setters and other builder methods - `pub` unless configured otherwise.
+    pub fn setter_vis(&self) -> syn::Visibility {
+        self.configured_setter_vis().unwrap_or_else(public_visibility)
+    }
+
+    /// Whether the field must be set in declaration order when the builder is in step mode.
+    pub fn is_step_field(&self) -> bool {
+        self.builder_attr.setter.skip.is_none() && self.builder_attr.via_mutators.is_none() && self.builder_attr.default.is_none()
+    }
+
     pub fn tuplized_type_ty_param(&self) -> syn::Type {
         let mut types = Punctuated::default();
         types.push(self.ty.clone());
@@ -87,6 +122,10 @@ impl<'a> FieldInfo<'a> {
     }
 
     pub fn setter_method_name(&self) -> Ident {
+        if let Some(name) = &self.builder_attr.setter.name {
+            return name.clone();
+        }
+
         let name = strip_raw_ident_prefix(self.name.to_string());
 
         if let (Some(prefix), Some(suffix)) = (&self.builder_attr.setter.prefix, &self.builder_attr.setter.suffix) {
@@ -100,23 +139,196 @@ impl<'a> FieldInfo<'a> {
         }
     }
 
+    /// The parameters of the field's setter, and the expression that computes the field's value from them.
+    pub fn setter_signature(&self) -> Result<SetterSignature, Error> {
+        let field_name = self.name;
+        let field_type = self.ty;
+        let setter = &self.builder_attr.setter;
+
+        let option_was_stripped;
+        let arg_type = if setter.strip_option.is_some() && setter.transform.is_none() {
+            if let Some(inner_type) = self.type_from_inside_option() {
+                option_was_stripped = true;
+                inner_type
+            } else if setter.strip_option.as_ref().is_some_and(|s| s.ignore_invalid) {
+                option_was_stripped = false;
+                field_type
+            } else {
+                return Err(Error::new_spanned(
+                    field_type,
+                    "can't `strip_option` - field is not `Option<...>`",
+                ));
+            }
+        } else {
+            option_was_stripped = false;
+            field_type
+        };
+        let (arg_type, arg_expr) = if let Some(Convert { trait_path, method, .. }) = &setter.convert {
+            (quote!(impl #trait_path), quote!(<_ as #trait_path>::#method(#field_name)))
+        } else if setter.auto_into.is_some() {
+            (quote!(impl ::core::convert::Into<#arg_type>), quote!(#field_name.into()))
+        } else {
+            (arg_type.to_token_stream(), field_name.to_token_stream())
+        };
+
+        Ok(if setter.strip_bool.is_some() {
+            SetterSignature {
+                generics: None,
+                params: quote!(),
+                value: quote!(true),
+            }
+        } else if let Some(transform) = &setter.transform {
+            let params = transform.params.iter().map(|(pat, ty)| quote!(#pat: #ty));
+            let body = &transform.body;
+
+            let body = match &transform.return_type {
+                syn::ReturnType::Default => quote!({ #body }),
+                syn::ReturnType::Type(_, ty) => quote!({
+                    let value: #ty = { #body };
+                    value
+                }),
+            };
+
+            SetterSignature {
+                generics: transform.generics.clone(),
+                params: quote!(#(#params),*),
+                value: body,
+            }
+        } else if option_was_stripped {
+            SetterSignature {
+                generics: None,
+                params: quote!(#field_name: #arg_type),
+                value: quote!(Some(#arg_expr)),
+            }
+        } else {
+            SetterSignature {
+                generics: None,
+                params: quote!(#field_name: #arg_type),
+                value: arg_expr,
+            }
+        })
+    }
+
+    /// Like [`Self::setter_signature`], for methods that take the parameters of several fields' setters. The parameters of
+    /// a `transform` are renamed to `__<field>_<param>`, so that the transforms of different fields can use the same
+    /// parameter names.
+    pub fn setter_signature_with_unique_params(&self) -> Result<SetterSignature, Error> {
+        let signature = self.setter_signature()?;
+        let Some(transform) = &self.builder_attr.setter.transform else {
+            return Ok(signature);
+        };
+        let field_name = strip_raw_ident_prefix(self.name.to_string());
+        let renamed = transform
+            .params
+            .iter()
+            .enumerate()
+            .map(|(i, (pat, _))| match pat {
+                syn::Pat::Ident(pat_ident) if pat_ident.subpat.is_none() => {
+                    format_ident!("__{}_{}", field_name, strip_raw_ident_prefix(pat_ident.ident.to_string()))
+                }
+                _ => format_ident!("__{}_{}", field_name, i),
+            })
+            .collect::<Vec<_>>();
+        let params = renamed
+            .iter()
+            .zip(&transform.params)
+            .map(|(name, (_, ty))| quote!(#name: #ty));
+        let pats = transform.params.iter().map(|(pat, _)| pat);
+        let value = signature.value;
+        Ok(SetterSignature {
+            generics: signature.generics,
+            params: quote!(#(#params),*),
+            value: quote!({
+                let (#(#pats,)*) = (#(#renamed,)*);
+                #value
+            }),
+        })
+    }
+
+    /// The type from which the field's value is created when it is not set through the setter (e.g. when deserialized),
+    /// and a function for converting that type to the field's type (or `None` if they are the same type).
+    ///
+    /// A single parameter `transform` is applied, and so is `strip_option` if `strip_option` is `true`.
+    pub fn dynamic_input(&self, strip_option: bool) -> (&syn::Type, Option<TokenStream>) {
+        let field_ty = self.ty;
+        let transform = self.builder_attr.setter.transform.as_ref().filter(|transform| {
+            transform.params.len() == 1
+                && transform.generics.as_ref().is_none_or(|generics| generics.params.is_empty())
+                && !matches!(transform.params[0].1, syn::Type::ImplTrait(_))
+        });
+        if let Some(transform) = transform {
+            let (pat, param_ty) = &transform.params[0];
+            let body = &transform.body;
+            (param_ty, Some(quote!(|#pat: #param_ty| -> #field_ty { #body })))
+        } else if let (true, Some(_), Some(inner_ty)) = (
+            strip_option,
+            &self.builder_attr.setter.strip_option,
+            self.type_from_inside_option(),
+        ) {
+            (inner_ty, Some(quote!(::core::option::Option::Some)))
+        } else {
+            (field_ty, None)
+        }
+    }
+
+    /// The name of the environment variable the field is read from by `from_env()`.
+    pub fn env_var_name(&self, prefix: &str) -> String {
+        if let Some(name) = &self.builder_attr.env_var {
+            name.value()
+        } else {
+            format!("{}{}", prefix, strip_raw_ident_prefix(self.name.to_string()).to_uppercase())
+        }
+    }
+
+    pub fn reset_method_name(&self) -> Ident {
+        Ident::new(
+            &format!("clear_{}", strip_raw_ident_prefix(self.name.to_string())),
+            Span::call_site(),
+        )
+    }
+
+    pub fn getter_method_names(&self) -> (Ident, Ident) {
+        let name = strip_raw_ident_prefix(self.name.to_string());
+        (
+            Ident::new(&format!("get_{}", name), Span::call_site()),
+            Ident::new(&format!("{}_mut", name), Span::call_site()),
+        )
+    }
+
     fn post_process(mut self) -> Result<Self, Error> {
+        if let Some(ref strip_option) = self.builder_attr.setter.strip_option
+            && let Some(negated) = &strip_option.negated
+        {
+            return Err(Error::new_spanned(negated, "negated is only supported for strip_bool"));
+        }
         if let Some(ref strip_bool) = self.builder_attr.setter.strip_bool {
-            if let Some(default_span) = self.builder_attr.default.as_ref().map(Spanned::span) {
+            // With a negated setter both values can be set explicitly, so the default may be `true`.
+            if strip_bool.negated.is_none()
+                && let Some(default_span) = self.builder_attr.default.as_ref().map(Spanned::span)
+            {
                 let mut error = Error::new(
                     strip_bool.span,
-                    "cannot set both strip_bool and default - default is assumed to be false",
+                    "cannot set both strip_bool and default without strip_bool(negated = ...) - default is assumed to be false",
                 );
                 error.combine(Error::new(default_span, "default set here"));
                 return Err(error);
             }
-            self.builder_attr.default = Some(syn::Expr::Lit(syn::ExprLit {
-                attrs: Default::default(),
-                lit: syn::Lit::Bool(syn::LitBool {
-                    value: false,
-                    span: strip_bool.span,
-                }),
-            }));
+            if self.builder_attr.default.is_none() {
+                self.builder_attr.default = Some(syn::Expr::Lit(syn::ExprLit {
+                    attrs: Default::default(),
+                    lit: syn::Lit::Bool(syn::LitBool {
+                        value: false,
+                        span: strip_bool.span,
+                    }),
+                }));
+            }
+        }
+        if let Some(vis) = self.configured_setter_vis() {
+            for mutator in &mut self.builder_attr.mutators {
+                if matches!(mutator.fun.vis, syn::Visibility::Inherited) {
+                    mutator.fun.vis = vis.clone();
+                }
+            }
         }
         if self.builder_attr.default.is_none()
             && let Some(default_where) = self.builder_attr.default_where.as_ref()
@@ -177,6 +389,21 @@ impl<'a> FieldInfo<'a> {
             where_clause
         };
 
+        // The builders created by presets hold the fields they set in `Preset` slots.
+        let preset_impl = self.set_by_preset.then(|| {
+            quote! {
+                #[allow(clippy::ref_option_ref)]
+                #[automatically_derived]
+                impl #impl_generics #crate_module_path::NextFieldDefault<(#(#dep_types,)* #crate_module_path::__private::Preset<#field_type>,)> for #struct_name #ty_generics #where_clause {
+                    type Output = #field_type;
+
+                    fn resolve((.., input,): (#(#dep_types,)* #crate_module_path::__private::Preset<#field_type>,)) -> Self::Output {
+                        input.0
+                    }
+                }
+            }
+        });
+
         Ok(Some(quote! {
             #[allow(clippy::ref_option_ref)]
             #[automatically_derived]
@@ -188,6 +415,8 @@ impl<'a> FieldInfo<'a> {
                 }
             }
 
+            #preset_impl
+
             #[allow(clippy::ref_option_ref)]
             #[automatically_derived]
             impl #impl_generics #crate_module_path::NextFieldDefault<(#(#dep_types,)* (),)> for #struct_name #ty_generics #where_clause_for_default {
@@ -201,6 +430,14 @@ impl<'a> FieldInfo<'a> {
     }
 }
 
+/// The parameters of a setter, and the expression that computes the field's value from them
+pub struct SetterSignature {
+    /// The setter's generic parameters and where clause, if it has any
+    pub generics: Option<syn::Generics>,
+    pub params: TokenStream,
+    pub value: TokenStream,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct FieldBuilderAttr<'a> {
     pub default: Option<syn::Expr>,
@@ -212,6 +449,9 @@ pub struct FieldBuilderAttr<'a> {
     /// Functions that are able to mutate fields in the builder that are already set
     pub mutators: Vec<Mutator>,
     pub mutable_during_default_resolution: Option<Span>,
+    pub getters: Option<Span>,
+    /// Overrides the name of the environment variable used by `from_env()`
+    pub env_var: Option<syn::LitStr>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -219,11 +459,20 @@ pub struct SetterSettings {
     pub doc: Option<syn::Expr>,
     pub skip: Option<Span>,
     pub auto_into: Option<Span>,
+    pub convert: Option<Convert>,
     pub strip_option: Option<Strip>,
     pub strip_bool: Option<Strip>,
     pub transform: Option<Transform>,
     pub prefix: Option<String>,
     pub suffix: Option<String>,
+    pub name: Option<Ident>,
+    /// Names of deprecated setters that behave like the field's setter
+    pub aliases: Vec<Ident>,
+    pub overridable: Option<Span>,
+    pub resettable: Option<Span>,
+    pub vis: Option<syn::Visibility>,
+    pub inherit_vis: Option<Span>,
+    pub generic: Option<Span>,
 }
 
 impl<'a> FieldBuilderAttr<'a> {
@@ -269,6 +518,12 @@ impl<'a> FieldBuilderAttr<'a> {
         }
 
         for mutator in self.mutators.iter_mut() {
+            if let Some(field) = mutator.set_fields.iter().find(|field| *field == name) {
+                return Err(Error::new_spanned(
+                    field,
+                    "a mutator declared on a field requires that field, so it cannot set it",
+                ));
+            }
             mutator.required_fields.insert(name.clone());
         }
 
@@ -295,6 +550,19 @@ impl<'a> FieldBuilderAttr<'a> {
             .filter_map(|(caption, span)| span.map(|span| (caption, span)))
             .collect::<Vec<_>>();
 
+        if let Some(convert) = &self.setter.convert {
+            let conflicting = [
+                ("into", self.setter.auto_into),
+                ("transform", self.setter.transform.as_ref().map(|t| t.span)),
+                ("strip_bool", self.setter.strip_bool.as_ref().map(|s| s.span)),
+            ];
+            if let Some((caption, span)) = conflicting.iter().find_map(|(caption, span)| Some((caption, (*span)?))) {
+                let mut error = Error::new(convert.span, format_args!("convert conflicts with {}", caption));
+                error.combine(Error::new(span, format_args!("{} set here", caption)));
+                return Err(error);
+            }
+        }
+
         if 1 < conflicting_transformations.len() {
             let (first_caption, first_span) = conflicting_transformations.pop().unwrap();
             let conflicting_captions = conflicting_transformations
@@ -358,6 +626,13 @@ impl ApplyMeta for FieldBuilderAttr<'_> {
                 &mut self.mutable_during_default_resolution,
                 "made mutable during default resolution",
             ),
+            "getters" => expr.apply_flag_to_field(&mut self.getters, "given getters"),
+            "from_env" => {
+                let mut settings = FieldFromEnvSettings::default();
+                settings.apply_sub_attr(expr.sub_attr()?)?;
+                self.env_var = settings.name;
+                Ok(())
+            }
             "via_mutators" => {
                 match expr {
                     AttrArg::Flag(ident) => {
@@ -440,8 +715,53 @@ impl ApplyMeta for SetterSettings {
                 };
                 Ok(())
             }
+            "name" => {
+                self.name = expr
+                    .key_value_or_not()?
+                    .map(|key_value| key_value.parse_value())
+                    .transpose()?;
+                Ok(())
+            }
+            "aliases" => {
+                let Some(key_value) = expr.key_value_or_not()? else {
+                    self.aliases.clear();
+                    return Ok(());
+                };
+                let elems = match key_value.parse_value()? {
+                    syn::Expr::Array(syn::ExprArray { elems, .. }) => elems,
+                    value => return Err(Error::new_spanned(value, "Expected an array of setter names")),
+                };
+                for elem in elems {
+                    match elem {
+                        syn::Expr::Path(path) if path.path.get_ident().is_some() => {
+                            self.aliases.push(path.path.get_ident().cloned().expect("should be ident"));
+                        }
+                        elem => return Err(Error::new_spanned(elem, "Expected setter name")),
+                    }
+                }
+                Ok(())
+            }
+            "vis" => {
+                self.vis = if let Some(key_value) = expr.key_value_or_not()? {
+                    Some(syn::parse_str(&expr_to_lit_string(&key_value.parse_value()?)?)?)
+                } else {
+                    None
+                };
+                Ok(())
+            }
+            "generic" => expr.apply_flag_to_field(&mut self.generic, "changing the type parameter"),
+            "inherit_vis" => expr.apply_flag_to_field(&mut self.inherit_vis, "inheriting the field's visibility"),
             "skip" => expr.apply_flag_to_field(&mut self.skip, "skipped"),
+            "overridable" => expr.apply_flag_to_field(&mut self.overridable, "overridable"),
+            "resettable" => expr.apply_flag_to_field(&mut self.resettable, "resettable"),
             "into" => expr.apply_flag_to_field(&mut self.auto_into, "calling into() on the argument"),
+            "convert" => {
+                self.convert = expr
+                    .key_value_or_not()?
+                    .map(|key_value| key_value.parse_value())
+                    .transpose()?;
+                Ok(())
+            }
             "strip_option" => {
                 expr.apply_potentialy_empty_sub_to_field(&mut self.strip_option, "putting the argument in Some(...)", Strip::new)
             }
@@ -461,6 +781,8 @@ impl ApplyMeta for SetterSettings {
 #[derive(Debug, Clone)]
 pub struct Strip {
     pub fallback: Option<syn::Ident>,
+    /// For `strip_bool` - the name of a zero arguments setter that sets the field to `false`
+    pub negated: Option<syn::Ident>,
     pub fallback_prefix: Option<String>,
     pub fallback_suffix: Option<String>,
     pub ignore_invalid: bool,
@@ -471,6 +793,7 @@ impl Strip {
     fn new(span: Span) -> Self {
         Self {
             fallback: None,
+            negated: None,
             fallback_prefix: None,
             fallback_suffix: None,
             ignore_invalid: false,
@@ -494,6 +817,17 @@ impl ApplyMeta for Strip {
                 self.fallback = Some(ident);
                 Ok(())
             }
+            "negated" => {
+                if self.negated.is_some() {
+                    return Err(Error::new_spanned(
+                        expr.name(),
+                        format!("Duplicate negated parameter {:?}", expr.name().to_string()),
+                    ));
+                }
+
+                self.negated = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
             "fallback_prefix" => {
                 if self.fallback_prefix.is_some() {
                     return Err(Error::new_spanned(
@@ -536,6 +870,34 @@ impl ApplyMeta for Strip {
     }
 }
 
+/// `setter(convert = Trait::method)` - the setter accepts `impl Trait` and calls `method` on it to get the field's value
+#[derive(Debug, Clone)]
+pub struct Convert {
+    pub trait_path: syn::Path,
+    pub method: syn::Ident,
+    span: Span,
+}
+
+impl syn::parse::Parse for Convert {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut trait_path: syn::Path = input.parse()?;
+        let span = trait_path.span();
+        let method = match trait_path.segments.pop().map(|pair| pair.into_value()) {
+            Some(syn::PathSegment {
+                ident,
+                arguments: syn::PathArguments::None,
+            }) if !trait_path.segments.is_empty() => ident,
+            _ => return Err(Error::new(span, "expected `Trait::method`")),
+        };
+        trait_path.segments.pop_punct();
+        Ok(Self {
+            trait_path,
+            method,
+            span,
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Transform {
     pub params: Vec<(syn::Pat, syn::Type)>,
@@ -606,6 +968,26 @@ fn parse_transform_closure(span: Span, expr: syn::Expr) -> Result<Transform, Err
     })
 }
 
+#[derive(Default)]
+struct FieldFromEnvSettings {
+    name: Option<syn::LitStr>,
+}
+
+impl ApplyMeta for FieldFromEnvSettings {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        match expr.name().to_string().as_str() {
+            "name" => {
+                self.name = Some(expr.key_value()?.parse_value()?);
+                Ok(())
+            }
+            _ => Err(Error::new_spanned(
+                expr.name(),
+                format!("Unknown parameter {:?}", expr.name().to_string()),
+            )),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ViaMutators {
     pub span: Span,
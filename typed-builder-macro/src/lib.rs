@@ -1,9 +1,18 @@
 use proc_macro2::TokenStream;
-use syn::{DeriveInput, parse::Error, parse_macro_input, spanned::Spanned};
+use quote::{quote, quote_spanned};
+use syn::{
+    DeriveInput,
+    parse::{Error, Parse, ParseStream},
+    parse_macro_input,
+    spanned::Spanned,
+};
 
 mod builder_attr;
+#[cfg(test)]
+mod expansion_size;
 mod field_info;
 mod mutator;
+mod preset;
 mod struct_info;
 mod util;
 
@@ -28,3 +37,133 @@ fn impl_my_derive(ast: &syn::DeriveInput) -> Result<TokenStream, Error> {
     };
     Ok(data)
 }
+
+#[proc_macro]
+pub fn build(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as syn::ExprStruct);
+    match impl_build(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn impl_build(input: &syn::ExprStruct) -> Result<TokenStream, Error> {
+    if let Some(qself) = &input.qself {
+        return Err(Error::new(qself.lt_token.span, "qualified paths are not supported"));
+    }
+    if let Some(rest) = &input.rest
+        && !matches!(rest.as_ref(), syn::Expr::Path(path) if path.path.is_ident("defaults"))
+    {
+        return Err(Error::new_spanned(
+            rest,
+            "only `..defaults` is supported - the fields that are not set get their defaults from the builder",
+        ));
+    }
+    let path = &input.path;
+    let setter_calls = input
+        .fields
+        .iter()
+        .map(|field| {
+            let syn::Member::Named(name) = &field.member else {
+                return Err(Error::new_spanned(&field.member, "tuple structs are not supported"));
+            };
+            let value = &field.expr;
+            Ok(quote_spanned!(name.span() => .#name(#value)))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let path_span = path.span();
+    let builder = quote_spanned!(path_span => <#path>::builder());
+    let build = quote_spanned!(path_span => .build());
+    Ok(quote!(#builder #(#setter_calls)* #build))
+}
+
+#[proc_macro_attribute]
+pub fn mutators(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let mut struct_path = parse_macro_input!(attr as syn::Path);
+    let item = parse_macro_input!(item as syn::ItemImpl);
+    let Some(last_segment) = struct_path.segments.last_mut() else {
+        return Error::new(struct_path.span(), "expected the path of a struct that derives TypedBuilder")
+            .to_compile_error()
+            .into();
+    };
+    last_segment.ident = util::external_mutators_macro_name(&last_segment.ident);
+    last_segment.arguments = syn::PathArguments::None;
+    let mut module_path = struct_path.clone();
+    module_path.segments.pop();
+    let module_path = if module_path.segments.is_empty() {
+        quote!()
+    } else {
+        let module_path = module_path.segments.pairs().map(|pair| pair.into_value());
+        let leading_colon = &struct_path.leading_colon;
+        quote!(#leading_colon #(#module_path)::*)
+    };
+    quote!(#struct_path! { (#module_path) #item }).into()
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __mutators_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ExternalMutatorsInput);
+    match impl_external_mutators(&input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct ExternalMutatorsInput {
+    ast: DeriveInput,
+    module_path: Option<syn::Path>,
+    mutators_impl: syn::ItemImpl,
+}
+
+impl Parse for ExternalMutatorsInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ast;
+        syn::braced!(ast in input);
+        let module_path;
+        syn::parenthesized!(module_path in input);
+        Ok(Self {
+            ast: ast.parse()?,
+            module_path: if module_path.is_empty() {
+                None
+            } else {
+                Some(module_path.call(syn::Path::parse_mod_style)?)
+            },
+            mutators_impl: input.parse()?,
+        })
+    }
+}
+
+fn impl_external_mutators(input: &ExternalMutatorsInput) -> Result<TokenStream, Error> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(fields),
+        ..
+    }) = &input.ast.data
+    else {
+        return Err(Error::new(
+            input.ast.span(),
+            "TypedBuilder mutators need a struct with named fields",
+        ));
+    };
+    let mutators_impl = &input.mutators_impl;
+    if let Some(trait_) = &mutators_impl.trait_ {
+        return Err(Error::new_spanned(&trait_.1, "mutators cannot be defined in a trait impl"));
+    }
+    let mutators = mutators_impl
+        .items
+        .iter()
+        .map(|item| match item {
+            syn::ImplItem::Fn(method) => mutator::Mutator::from_fn(syn::ItemFn {
+                attrs: method.attrs.clone(),
+                vis: method.vis.clone(),
+                sig: method.sig.clone(),
+                block: Box::new(method.block.clone()),
+            }),
+            _ => Err(Error::new_spanned(
+                item,
+                "only mutator functions are allowed in a mutators impl block",
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    struct_info::StructInfo::new(&input.ast, fields.named.iter())?.derive_external_mutators(&mutators, input.module_path.clone())
+}
@@ -4,7 +4,7 @@ use proc_macro2::Ident;
 use syn::{
     Error, Expr, FnArg, ItemFn, PatIdent, ReturnType, Signature, Token, Type,
     parse::{Parse, ParseStream},
-    parse_quote,
+    parse_quote, parse_quote_spanned,
     punctuated::Punctuated,
     spanned::Spanned,
 };
@@ -15,37 +15,49 @@ use crate::util::{ApplyMeta, AttrArg, pat_to_ident};
 pub struct Mutator {
     pub fun: ItemFn,
     pub required_fields: HashSet<Ident>,
+    /// Fields that must be unset before calling the mutator, and whose values the mutator returns - in this order
+    pub set_fields: Vec<Ident>,
 }
 
 #[derive(Default)]
 struct MutatorAttribute {
     requires: HashSet<Ident>,
+    sets: Vec<Ident>,
 }
 
 impl ApplyMeta for MutatorAttribute {
     fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
-        if expr.name() != "requires" {
-            return Err(Error::new_spanned(expr.name(), "Only `requires` is supported"));
+        let name = expr.name().to_string();
+        if name != "requires" && name != "sets" {
+            return Err(Error::new_spanned(expr.name(), "Only `requires` and `sets` are supported"));
         }
 
-        match expr.key_value()?.parse_value()? {
-            Expr::Array(syn::ExprArray { elems, .. }) => self.requires.extend(
-                elems
-                    .into_iter()
-                    .map(|expr| match expr {
-                        Expr::Path(path) if path.path.get_ident().is_some() => {
-                            Ok(path.path.get_ident().cloned().expect("should be ident"))
-                        }
-                        expr => Err(Error::new_spanned(expr, "Expected field name")),
-                    })
-                    .collect::<Result<Vec<_>, _>>()?,
-            ),
+        let fields = match expr.key_value()?.parse_value()? {
+            Expr::Array(syn::ExprArray { elems, .. }) => elems
+                .into_iter()
+                .map(|expr| match expr {
+                    Expr::Path(path) if path.path.get_ident().is_some() => {
+                        Ok(path.path.get_ident().cloned().expect("should be ident"))
+                    }
+                    expr => Err(Error::new_spanned(expr, "Expected field name")),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
             expr => {
                 return Err(Error::new_spanned(
                     expr,
                     "Only list of field names [field1, field2, …] supported",
                 ));
             }
+        };
+        if name == "requires" {
+            self.requires.extend(fields);
+        } else {
+            for field in fields {
+                if self.sets.contains(&field) {
+                    return Err(Error::new_spanned(field, "field is already listed in `sets`"));
+                }
+                self.sets.push(field);
+            }
         }
         Ok(())
     }
@@ -53,8 +65,51 @@ impl ApplyMeta for MutatorAttribute {
 
 impl Parse for Mutator {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut fun: ItemFn = input.parse()?;
+        Self::from_fn(input.parse()?)
+    }
+}
+
+/// How the return value of the mutator function affects the return value of the builder method
+pub enum MutatorOutput {
+    /// Returns `()` - the builder method returns the builder
+    Builder,
+    /// Returns `R` - the builder method returns `(builder, R)`
+    WithValue,
+    /// Returns `Result<(), E>` - the builder method returns `Result<builder, E>`
+    Fallible,
+    /// Returns `Result<R, E>` - the builder method returns `Result<(builder, R), E>`
+    FallibleWithValue,
+    /// Returns the values of the `sets` fields - the builder method returns the builder with them set
+    SetsFields,
+    /// Returns `Result<V, E>` where `V` are the values of the `sets` fields - the builder method returns
+    /// `Result<builder, E>`
+    FallibleSetsFields,
+}
+
+fn is_unit(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(tuple) if tuple.elems.is_empty())
+}
 
+/// The first generic argument of a `Result<...>` type (or a type alias named `Result`, like `io::Result`)
+fn result_ok_type_mut(ty: &mut Type) -> Option<&mut Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last_mut()?;
+    if segment.ident != "Result" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &mut segment.arguments else {
+        return None;
+    };
+    match args.args.first_mut()? {
+        syn::GenericArgument::Type(ok_type) => Some(ok_type),
+        _ => None,
+    }
+}
+
+impl Mutator {
+    pub fn from_fn(mut fun: ItemFn) -> syn::Result<Self> {
         let mut attribute = MutatorAttribute::default();
 
         let mut i = 0;
@@ -68,9 +123,14 @@ impl Parse for Mutator {
             }
         }
 
-        // Ensure `&mut self` receiver
+        // Ensure `&mut self` receiver. The original `self` token is kept, because its hygiene needs to match the `self`
+        // tokens in the function's body.
         if let Some(FnArg::Receiver(receiver)) = fun.sig.inputs.first_mut() {
-            *receiver = parse_quote!(&mut self);
+            let span = receiver.self_token.span;
+            receiver.reference = Some((Token![&](span), None));
+            receiver.mutability = Some(Token![mut](span));
+            receiver.colon_token = None;
+            receiver.ty = parse_quote_spanned!(span => &mut Self);
         } else {
             // Error either on first argument or `()`
             return Err(syn::Error::new(
@@ -83,17 +143,112 @@ impl Parse for Mutator {
             ));
         };
 
-        Ok(Self {
+        if let Some(field) = attribute.sets.iter().find(|field| attribute.requires.contains(field)) {
+            return Err(Error::new_spanned(
+                field,
+                format!("`{}` cannot be both required and set by the mutator", field),
+            ));
+        }
+
+        let mutator = Self {
             fun,
             required_fields: attribute.requires,
-        })
+            set_fields: attribute.sets,
+        };
+        mutator.validate_set_fields_output()?;
+        Ok(mutator)
+    }
+
+    /// A mutator that sets fields must return their values, so that the compiler checks it sets all of them.
+    fn validate_set_fields_output(&self) -> syn::Result<()> {
+        if self.set_fields.is_empty() {
+            return Ok(());
+        }
+        let values_type = match (self.output(), &self.fun.sig.output) {
+            (MutatorOutput::SetsFields, ReturnType::Type(_, ty)) => ty.as_ref().clone(),
+            (MutatorOutput::FallibleSetsFields, ReturnType::Type(_, ty)) => {
+                let mut ty = ty.as_ref().clone();
+                result_ok_type_mut(&mut ty)
+                    .expect("fallible mutators return a `Result`")
+                    .clone()
+            }
+            _ => {
+                return Err(Error::new(
+                    match &self.fun.sig.output {
+                        ReturnType::Default => self.fun.sig.paren_token.span.close(),
+                        ReturnType::Type(_, ty) => ty.span(),
+                    },
+                    format!(
+                        "a mutator with `sets` must return {} - possibly wrapped in a `Result`",
+                        self.set_fields_description(),
+                    ),
+                ));
+            }
+        };
+        if let (Type::Tuple(tuple), 2..) = (&values_type, self.set_fields.len())
+            && tuple.elems.len() != self.set_fields.len()
+        {
+            return Err(Error::new_spanned(
+                tuple,
+                format!("expected {}", self.set_fields_description()),
+            ));
+        }
+        Ok(())
+    }
+
+    fn set_fields_description(&self) -> String {
+        match self.set_fields.as_slice() {
+            [field] => format!("the value of `{}`", field),
+            fields => format!(
+                "the values of `({})`, in the order of `sets`",
+                fields.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+            ),
+        }
+    }
+
+    pub fn output(&self) -> MutatorOutput {
+        let ReturnType::Type(_, ty) = &self.fun.sig.output else {
+            return MutatorOutput::Builder;
+        };
+        let mut ty = ty.as_ref().clone();
+        let sets_fields = !self.set_fields.is_empty();
+        if is_unit(&ty) {
+            MutatorOutput::Builder
+        } else if let Some(ok_type) = result_ok_type_mut(&mut ty) {
+            if is_unit(ok_type) {
+                MutatorOutput::Fallible
+            } else if sets_fields {
+                MutatorOutput::FallibleSetsFields
+            } else {
+                MutatorOutput::FallibleWithValue
+            }
+        } else if sets_fields {
+            MutatorOutput::SetsFields
+        } else {
+            MutatorOutput::WithValue
+        }
     }
-}
 
-impl Mutator {
     /// Signature for Builder::<mutator> function
-    pub fn outer_sig(&self, output: Type) -> Signature {
+    pub fn outer_sig(&self, builder: Type) -> Signature {
         let mut sig = self.fun.sig.clone();
+        let output = match (self.output(), &self.fun.sig.output) {
+            (MutatorOutput::Builder | MutatorOutput::SetsFields, _) | (_, ReturnType::Default) => builder,
+            (MutatorOutput::WithValue, ReturnType::Type(_, ty)) => parse_quote!((#builder, #ty)),
+            (
+                MutatorOutput::Fallible | MutatorOutput::FallibleWithValue | MutatorOutput::FallibleSetsFields,
+                ReturnType::Type(_, ty),
+            ) => {
+                let mut ty = ty.as_ref().clone();
+                let ok_type = result_ok_type_mut(&mut ty).expect("fallible mutators return a `Result`");
+                *ok_type = if is_unit(ok_type) || matches!(self.output(), MutatorOutput::FallibleSetsFields) {
+                    builder
+                } else {
+                    parse_quote!((#builder, #ok_type))
+                };
+                ty
+            }
+        };
         sig.output = ReturnType::Type(Default::default(), output.into());
 
         sig.inputs = sig
@@ -0,0 +1,38 @@
+use proc_macro2::{Ident, Span};
+use syn::parse::Error;
+
+use crate::util::{ApplyMeta, AttrArg};
+
+/// A named set of field values, for which a method that creates a builder with these fields already set is generated
+#[derive(Debug, Clone)]
+pub struct Preset {
+    pub name: Option<Ident>,
+    pub span: Span,
+    pub fields: Vec<(Ident, syn::Expr)>,
+}
+
+impl Preset {
+    pub fn new(span: Span) -> Self {
+        Self {
+            name: None,
+            span,
+            fields: Vec::new(),
+        }
+    }
+}
+
+impl ApplyMeta for Preset {
+    fn apply_meta(&mut self, expr: AttrArg) -> Result<(), Error> {
+        let key_value = expr.key_value()?;
+        if key_value.name == "name" {
+            self.name = Some(key_value.parse_value()?);
+        } else {
+            let field = key_value.name.clone();
+            if self.fields.iter().any(|(existing, _)| *existing == field) {
+                return Err(Error::new_spanned(field, "field already set by this preset"));
+            }
+            self.fields.push((field, key_value.parse_value()?));
+        }
+        Ok(())
+    }
+}
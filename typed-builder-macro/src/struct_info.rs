@@ -3,17 +3,35 @@ use quote::{ToTokens, format_ident, quote};
 use syn::{GenericArgument, ItemFn, Token, parse::Error, parse_quote, punctuated::Punctuated};
 
 use crate::{
-    builder_attr::{IntoSetting, TypeBuilderAttr},
-    field_info::FieldInfo,
-    mutator::Mutator,
+    builder_attr::{BuilderStorage, CommonDeclarationSettings, IntoSetting, PatchTypeSettings, TypeBuilderAttr},
+    field_info::{FieldInfo, SetterSignature},
+    mutator::{Mutator, MutatorOutput},
+    preset::Preset,
     util::{
-        empty_type, empty_type_tuple, first_visibility, modify_types_generics_hack, phantom_data_for_generics, public_visibility,
-        strip_raw_ident_prefix, type_tuple,
+        ReplaceTypeParam, empty_type, empty_type_tuple, external_mutators_macro_name, first_visibility, ident_to_type,
+        modify_types_generics_hack, phantom_data_for_generics, public_visibility, strip_raw_ident_prefix, tokens_mention_ident,
+        type_tuple,
     },
 };
 
+/// How the values of the fields are held while resolving their defaults
+#[derive(Clone, Copy)]
+enum FieldValues {
+    /// Each field's variable holds its slot in the builder's fields tuple - `(T,)` if it was set and `()` if not. The
+    /// types of the defaulted fields' slots are their generic parameters from [`FieldInfo::type_ident`].
+    BuilderState,
+    /// Each field's variable is an `Option<T>`.
+    Optional,
+    /// The fields are still in the in-place storage of the builder named `__self`, which they are taken out of one by
+    /// one - since a `const fn` cannot move a value that needs dropping out of a tuple or a variable's `Option`.
+    Storage,
+    /// Only the required fields have variables, which hold their values. All the other fields are unset.
+    RequiredOnly,
+}
+
 #[derive(Debug)]
 pub struct StructInfo<'a> {
+    input: &'a syn::DeriveInput,
     vis: &'a syn::Visibility,
     pub name: &'a syn::Ident,
     pub generics: &'a syn::Generics,
@@ -27,9 +45,38 @@ impl<'a> StructInfo<'a> {
     fn included_fields(&self) -> impl Iterator<Item = &FieldInfo<'a>> {
         self.fields.iter().filter(|f| f.builder_attr.setter.skip.is_none())
     }
+    fn required_fields(&self) -> impl Iterator<Item = &FieldInfo<'a>> {
+        self.included_fields()
+            .filter(|f| f.builder_attr.default.is_none() && f.builder_attr.via_mutators.is_none())
+    }
     fn setter_fields(&self) -> impl Iterator<Item = &FieldInfo<'a>> {
         self.included_fields().filter(|f| f.builder_attr.via_mutators.is_none())
     }
+    /// In step mode - the fields that must be set in declaration order.
+    fn step_fields(&self) -> impl Iterator<Item = &FieldInfo<'a>> {
+        self.setter_fields()
+            .filter(|f| self.builder_attr.step.is_some() && f.is_step_field())
+    }
+    /// In step mode - whether the field is optional, in which case its slot is an `Option` in every state of the builder.
+    fn is_step_optional(&self, field: &FieldInfo) -> bool {
+        self.builder_attr.step.is_some() && !field.is_step_field()
+    }
+    /// In step mode - the slots of the builder's state in which the first `set` step fields are set.
+    fn step_state(&self, set: usize) -> syn::TypeTuple {
+        let mut step_index = 0;
+        type_tuple(self.included_fields().map(|f| {
+            if !f.is_step_field() {
+                let ty = f.ty;
+                return parse_quote!(::core::option::Option<#ty>);
+            }
+            step_index += 1;
+            if step_index <= set {
+                f.tuplized_type_ty_param()
+            } else {
+                empty_type()
+            }
+        }))
+    }
 
     fn generic_arguments(&self) -> Punctuated<GenericArgument, Token![,]> {
         self.generics
@@ -57,19 +104,440 @@ impl<'a> StructInfo<'a> {
             .get_name()
             .map(|name| strip_raw_ident_prefix(name.to_string()))
             .unwrap_or_else(|| strip_raw_ident_prefix(format!("{}Builder", ast.ident)));
+        let mut fields = fields
+            .enumerate()
+            .map(|(i, f)| FieldInfo::new(i, f, builder_attr.field_defaults.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // A `const fn` cannot call trait methods or closures, so the options that need them are not available in `const`
+        // builders.
+        if let Some(const_span) = builder_attr.const_builder {
+            let struct_level = [
+                ("builder_type(debug)", builder_attr.builder_type.debug.is_some()),
+                (
+                    "build_method(into)",
+                    !matches!(builder_attr.build_method.into, IntoSetting::NoConversion),
+                ),
+                ("mutators", !builder_attr.mutators.is_empty()),
+                ("external_mutators", builder_attr.external_mutators.is_some()),
+                ("preset", !builder_attr.presets.is_empty()),
+            ];
+            if let Some((caption, _)) = struct_level.iter().find(|(_, used)| *used) {
+                return Err(Error::new(
+                    const_span,
+                    format!("`{caption}` is not supported in a `const` builder"),
+                ));
+            }
+            for field in &fields {
+                let attr = &field.builder_attr;
+                let field_level = [
+                    ("into", attr.setter.auto_into.is_some()),
+                    ("convert", attr.setter.convert.is_some()),
+                    ("transform", attr.setter.transform.is_some()),
+                    ("default_where", attr.default_where.is_some()),
+                    ("setter(generic)", attr.setter.generic.is_some()),
+                    ("setter(overridable)", attr.setter.overridable.is_some()),
+                    ("setter(resettable)", attr.setter.resettable.is_some()),
+                    ("getters", attr.getters.is_some()),
+                    ("via_mutators", attr.via_mutators.is_some()),
+                    ("mutators", !attr.mutators.is_empty()),
+                ];
+                if let Some((caption, _)) = field_level.iter().find(|(_, used)| *used) {
+                    return Err(Error::new_spanned(
+                        field.name,
+                        format!("`{caption}` is not supported in a `const` builder"),
+                    ));
+                }
+            }
+        }
+
+        // In-place storage keeps the fields out of the builder's type parameter, which `setter(generic)` changes and the
+        // external mutators' callback converts.
+        if builder_attr.builder_type.storage == BuilderStorage::InPlace {
+            if let Some(external_mutators_span) = builder_attr.external_mutators {
+                return Err(Error::new(
+                    external_mutators_span,
+                    "`external_mutators` is not supported with `builder_type(storage = \"in_place\")`",
+                ));
+            }
+            if let Some(field) = fields.iter().find(|f| f.builder_attr.setter.generic.is_some()) {
+                return Err(Error::new_spanned(
+                    field.name,
+                    "`setter(generic)` is not supported with `builder_type(storage = \"in_place\")`",
+                ));
+            }
+        }
+
+        // In `step` mode, each state of the builder is a concrete type - so the required fields are always set in order,
+        // and the options that need impls that are generic over the builder's state are not available.
+        if let Some(step_span) = builder_attr.step {
+            let struct_level = [
+                ("mutators", !builder_attr.mutators.is_empty()),
+                ("external_mutators", builder_attr.external_mutators.is_some()),
+            ];
+            if let Some((caption, _)) = struct_level.iter().find(|(_, used)| *used) {
+                return Err(Error::new(step_span, format!("`{caption}` is not supported in `step` mode")));
+            }
+            for field in &fields {
+                let attr = &field.builder_attr;
+                let field_level = [
+                    ("default_where", attr.default_where.is_some()),
+                    ("setter(generic)", attr.setter.generic.is_some()),
+                    ("getters", attr.getters.is_some()),
+                    ("via_mutators", attr.via_mutators.is_some()),
+                    ("mutators", !attr.mutators.is_empty()),
+                ];
+                if let Some((caption, _)) = field_level.iter().find(|(_, used)| *used) {
+                    return Err(Error::new_spanned(
+                        field.name,
+                        format!("`{caption}` is not supported in `step` mode"),
+                    ));
+                }
+                let required_level = [
+                    ("setter(overridable)", attr.setter.overridable.is_some()),
+                    ("setter(resettable)", attr.setter.resettable.is_some()),
+                ];
+                if let (None, Some((caption, _))) = (&attr.default, required_level.iter().find(|(_, used)| *used)) {
+                    return Err(Error::new_spanned(
+                        field.name,
+                        format!("in `step` mode, required fields are set in order and cannot be `{caption}`"),
+                    ));
+                }
+            }
+        }
+
+        // The builders created by presets hold the fields they set in `Preset` slots, which the fields' setters accept so
+        // that the preset's values can be overridden. In `step` mode, presets can only set optional fields - which the
+        // setters can override in every state.
+        for preset in &builder_attr.presets {
+            for (preset_field, _) in &preset.fields {
+                let preset_field_name = strip_raw_ident_prefix(preset_field.to_string());
+                let Some(field) = fields
+                    .iter_mut()
+                    .find(|f| strip_raw_ident_prefix(f.name.to_string()) == preset_field_name)
+                else {
+                    return Err(Error::new_spanned(preset_field, "no such field"));
+                };
+                let setter = &field.builder_attr.setter;
+                if setter.skip.is_some() || field.builder_attr.via_mutators.is_some() {
+                    return Err(Error::new_spanned(
+                        preset_field,
+                        "presets can only set fields that have setters",
+                    ));
+                }
+                if setter.strip_bool.is_some() || setter.transform.as_ref().is_some_and(|t| t.params.len() != 1) {
+                    return Err(Error::new_spanned(
+                        preset_field,
+                        "presets can only set fields whose setters take a single argument",
+                    ));
+                }
+                if builder_attr.step.is_some() {
+                    if field.builder_attr.default.is_none() {
+                        return Err(Error::new_spanned(
+                            preset_field,
+                            "in `step` mode, presets can only set optional fields",
+                        ));
+                    }
+                } else {
+                    field.set_by_preset = true;
+                }
+            }
+        }
+
+        let mut step_fields = fields.iter().filter(|f| builder_attr.step.is_some() && f.is_step_field());
+        let mut seen_args = Vec::new();
+        for arg in &builder_attr.builder_method.args {
+            if seen_args.contains(&arg) {
+                return Err(Error::new_spanned(arg, "field already listed in `args`"));
+            }
+            seen_args.push(arg);
+            let Some(field) = fields.iter().find(|f| f.name == arg) else {
+                return Err(Error::new_spanned(arg, "no such field"));
+            };
+            let setter = &field.builder_attr.setter;
+            if setter.skip.is_some() || field.builder_attr.via_mutators.is_some() {
+                return Err(Error::new_spanned(
+                    arg,
+                    "only fields that have setters can be builder method arguments",
+                ));
+            }
+            if setter.strip_bool.is_some() {
+                return Err(Error::new_spanned(
+                    arg,
+                    "`strip_bool` fields cannot be builder method arguments",
+                ));
+            }
+            if builder_attr.step.is_some() && field.is_step_field() && step_fields.next().map(|f| f.name) != Some(arg) {
+                return Err(Error::new_spanned(
+                    arg,
+                    "in `step` mode, the required fields in `args` must be the first required fields, in order",
+                ));
+            }
+        }
+
+        // `MissingFields` tracks the missing required fields in a `u128`.
+        if builder_attr.patch.is_some() || builder_attr.deserialize.is_some() {
+            let mut required_fields = fields.iter().filter(|f| {
+                f.builder_attr.setter.skip.is_none() && f.builder_attr.default.is_none() && f.builder_attr.via_mutators.is_none()
+            });
+            if let Some(field) = required_fields.nth(128) {
+                return Err(Error::new_spanned(
+                    field.name,
+                    "`patch` and `deserialize` support at most 128 required fields",
+                ));
+            }
+        }
+
+        // The orphan rules do not allow implementing `From` for a bare type parameter.
+        if let (Some(_), IntoSetting::TypeConversionToSpecificType(into)) =
+            (builder_attr.build_method.impl_from, &builder_attr.build_method.into)
+            && into.qself.is_none()
+            && ast.generics.type_params().any(|param| into.path.is_ident(&param.ident))
+        {
+            return Err(Error::new_spanned(
+                into,
+                "`build_method(impl_from)` cannot implement `From` for a type parameter of the struct",
+            ));
+        }
+
+        for field in &fields {
+            let Some(span) = field.builder_attr.setter.generic else {
+                continue;
+            };
+            let Some(param) = field
+                .generic_setter_param()
+                .filter(|&param| ast.generics.type_params().any(|type_param| type_param.ident == *param))
+            else {
+                return Err(Error::new(
+                    span,
+                    "`setter(generic)` requires the field's type to be one of the struct's type parameters",
+                ));
+            };
+            let setter = &field.builder_attr.setter;
+            if setter.auto_into.is_some()
+                || setter.convert.is_some()
+                || setter.transform.is_some()
+                || setter.strip_option.is_some()
+                || setter.strip_bool.is_some()
+            {
+                return Err(Error::new(
+                    span,
+                    "`setter(generic)` cannot be combined with `into`, `convert`, `transform`, `strip_option` or `strip_bool`",
+                ));
+            }
+            for other in &fields {
+                if other.ordinal == field.ordinal
+                    || other.builder_attr.setter.skip.is_some()
+                    || !tokens_mention_ident(other.ty, param)
+                {
+                    continue;
+                }
+                if other.builder_attr.via_mutators.is_some() {
+                    return Err(Error::new_spanned(
+                        other.name,
+                        format!("`via_mutators` fields cannot use `{param}`, which is changed by `setter(generic)`"),
+                    ));
+                }
+                if other.builder_attr.setter.generic.is_some() {
+                    return Err(Error::new_spanned(
+                        other.name,
+                        format!("`{param}` is already changed by the `setter(generic)` of `{}`", field.name),
+                    ));
+                }
+            }
+        }
+
         Ok(StructInfo {
+            input: ast,
             vis: &ast.vis,
             name: &ast.ident,
             generics: &ast.generics,
-            fields: fields
-                .enumerate()
-                .map(|(i, f)| FieldInfo::new(i, f, builder_attr.field_defaults.clone()))
-                .collect::<Result<_, _>>()?,
+            fields,
             builder_attr,
             builder_name: syn::Ident::new(&builder_name, proc_macro2::Span::call_site()),
         })
     }
 
+    /// The value of a field's slot in a new builder - empty, unless the field is `via_mutators`. In step mode, the optional
+    /// fields' slots are `None`.
+    fn initial_field_slot(&self, field: &FieldInfo) -> TokenStream {
+        if self.is_step_optional(field) {
+            return quote!(::core::option::Option::None);
+        }
+        field.builder_attr.via_mutators.as_ref().map_or_else(
+            || quote!(()),
+            |via_mutators| {
+                let init = &via_mutators.init;
+                quote!((#init,))
+            },
+        )
+    }
+
+    /// The slot type of a field in impls that need the field to be set. The builders created by presets hold the fields
+    /// they set in `Preset` slots, so the slots of these fields are made generic, with a `SetSlot` bound.
+    fn set_field_slot(&self, field: &FieldInfo, generics: &mut syn::Generics) -> syn::Type {
+        if !field.set_by_preset {
+            return field.tuplized_type_ty_param();
+        }
+        generics.params.push(field.generic_ty_param());
+        let predicate = self.slot_predicate(field, "SetSlot");
+        generics.make_where_clause().predicates.push(predicate);
+        field.type_ident()
+    }
+
+    /// The value in the slot of a field that is set, where the slot's type is given by [`Self::set_field_slot`].
+    fn set_field_value(&self, field: &FieldInfo, slot: TokenStream) -> TokenStream {
+        if field.set_by_preset {
+            let StructInfo { name, .. } = self;
+            let (_, ty_generics, _) = self.generics.split_for_impl();
+            let crate_module_path = &self.builder_attr.crate_module_path;
+            let (type_ident, ty) = (field.type_ident(), field.ty);
+            quote!(<#type_ident as #crate_module_path::__private::SetSlot<#name #ty_generics, #ty>>::into_value(#slot))
+        } else {
+            quote!(#slot.0)
+        }
+    }
+
+    /// Bound the generic slot of a field with one of the `__private` slot traits, which take the struct's type.
+    fn slot_predicate(&self, field: &FieldInfo, slot_trait: &str) -> syn::WherePredicate {
+        let StructInfo { name, .. } = self;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let crate_module_path = &self.builder_attr.crate_module_path;
+        let slot_trait = syn::Ident::new(slot_trait, proc_macro2::Span::call_site());
+        let (type_ident, ty) = (field.type_ident(), field.ty);
+        parse_quote!(#type_ident: #crate_module_path::__private::#slot_trait<#name #ty_generics, #ty>)
+    }
+
+    /// Builders with `builder_type(storage = "in_place")` store their fields in a struct with an `Option` per field, which
+    /// the setters write in place. So do `const` builders, since a `const fn` cannot destructure a builder whose slots -
+    /// with generic types that may need dropping - are its fields. The builder's type parameter only tracks which fields
+    /// are set.
+    fn in_place(&self) -> bool {
+        self.builder_attr.builder_type.storage == BuilderStorage::InPlace || self.builder_attr.const_builder.is_some()
+    }
+
+    fn const_token(&self) -> Option<Token![const]> {
+        self.builder_attr.const_builder.map(Token![const])
+    }
+
+    /// The struct that holds the fields of builders with in-place storage.
+    fn storage_name(&self) -> syn::Ident {
+        format_ident!("{}Storage", self.builder_name)
+    }
+
+    /// Construct the builder from the slots of its fields, which have the given types.
+    fn builder_from_slots(
+        &self,
+        builder_name: &syn::Path,
+        slots: impl IntoIterator<Item = impl ToTokens>,
+        slot_types: &syn::TypeTuple,
+        phantom: TokenStream,
+    ) -> TokenStream {
+        let slots = slots.into_iter().collect::<Vec<_>>();
+        if !self.in_place() {
+            return quote! {
+                #builder_name {
+                    fields: (#(#slots,)*),
+                    phantom: #phantom,
+                }
+            };
+        }
+        let mut storage_name = builder_name.clone();
+        if let Some(last_segment) = storage_name.segments.last_mut() {
+            last_segment.ident = self.storage_name();
+        }
+        let names = self.included_fields().map(|f| f.name).collect::<Vec<_>>();
+        // The empty slots are not read, so they are not bound.
+        let bindings = self.included_fields().zip(&slot_types.elems).map(|(field, slot_type)| {
+            if *slot_type == empty_type() {
+                quote!(_)
+            } else {
+                field.name.to_token_stream()
+            }
+        });
+        let options = self.included_fields().zip(&slot_types.elems).map(|(field, slot_type)| {
+            let name = field.name;
+            if self.is_step_optional(field) {
+                name.to_token_stream()
+            } else if *slot_type == empty_type() {
+                quote!(::core::option::Option::None)
+            } else {
+                quote!(::core::option::Option::Some(#name.0))
+            }
+        });
+        let mut generic_arguments = self.generic_arguments();
+        generic_arguments.push(syn::GenericArgument::Type(slot_types.clone().into()));
+        quote! {{
+            let (#(#bindings,)*) = (#(#slots,)*);
+            #builder_name::<#generic_arguments> {
+                fields: #storage_name {
+                    #(#names: #options,)*
+                    __phantom: ::core::marker::PhantomData,
+                },
+                phantom: ::core::marker::PhantomData,
+            }
+        }}
+    }
+
+    /// The struct that holds the fields of builders with in-place storage, and the builder's `Clone` implementation -
+    /// which, like with tuple storage, only requires the set fields to be `Clone`.
+    fn storage_impl(&self) -> TokenStream {
+        let StructInfo { ref builder_name, .. } = *self;
+        let crate_module_path = &self.builder_attr.crate_module_path;
+        let storage_name = self.storage_name();
+        let vis = first_visibility(&[self.builder_attr.builder_type.common.vis.as_ref(), Some(self.vis)]);
+        let (_, ty_generics, where_clause) = self.generics.split_for_impl();
+        let names = self.included_fields().map(|f| f.name).collect::<Vec<_>>();
+        let types = self.included_fields().map(|f| f.ty);
+        let phantom_data = phantom_data_for_generics(self.generics);
+        let generics = self.generics;
+        let builder_type_attributes = &self.builder_attr.builder_type.attributes;
+        let storage_turbofish = ty_generics.as_turbofish();
+
+        let mut clone_generics = self.generics.clone();
+        let mut slot_types = empty_type_tuple();
+        for field in self.included_fields() {
+            let (type_ident, ty) = (field.type_ident(), field.ty);
+            clone_generics.params.push(field.generic_ty_param());
+            clone_generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#type_ident: #crate_module_path::__private::CloneInPlaceSlot<#ty>));
+            slot_types.elems.push(type_ident);
+        }
+        let (clone_impl_generics, _, clone_where_clause) = clone_generics.split_for_impl();
+        let mut clone_ty_generics = self.generic_arguments();
+        clone_ty_generics.push(syn::GenericArgument::Type(slot_types.clone().into()));
+        let cloned_slots = self.included_fields().zip(&slot_types.elems).map(|(field, slot_type)| {
+            let FieldInfo { name, ty, .. } = field;
+            quote!(<#slot_type as #crate_module_path::__private::CloneInPlaceSlot<#ty>>::clone_in_place(&self.fields.#name))
+        });
+
+        quote! {
+            #[doc(hidden)]
+            #[allow(dead_code, non_camel_case_types, non_snake_case)]
+            #(#builder_type_attributes)*
+            #vis struct #storage_name #generics #where_clause {
+                #(#names: ::core::option::Option<#types>,)*
+                __phantom: #phantom_data,
+            }
+
+            #[automatically_derived]
+            impl #clone_impl_generics Clone for #builder_name <#clone_ty_generics> #clone_where_clause {
+                #[allow(clippy::default_trait_access)]
+                fn clone(&self) -> Self {
+                    Self {
+                        fields: #storage_name #storage_turbofish {
+                            #(#names: #cloned_slots,)*
+                            __phantom: ::core::default::Default::default(),
+                        },
+                        phantom: ::core::default::Default::default(),
+                    }
+                }
+            }
+        }
+    }
+
     fn builder_creation_impl(&self) -> syn::Result<TokenStream> {
         let StructInfo {
             vis,
@@ -78,22 +546,52 @@ impl<'a> StructInfo<'a> {
             ..
         } = *self;
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let init_fields_type = type_tuple(self.included_fields().map(|f| {
-            if f.builder_attr.via_mutators.is_some() {
-                f.tuplized_type_ty_param()
+        let is_arg = |f: &FieldInfo| self.builder_attr.builder_method.args.iter().any(|arg| arg == f.name);
+        let init_fields_type = if self.builder_attr.step.is_some() {
+            self.step_state(self.step_fields().filter(|f| is_arg(f)).count())
+        } else {
+            type_tuple(self.included_fields().map(|f| {
+                if f.builder_attr.via_mutators.is_some() || is_arg(f) {
+                    f.tuplized_type_ty_param()
+                } else {
+                    empty_type()
+                }
+            }))
+        };
+        let init_fields_expr = self.included_fields().map(|f| {
+            if is_arg(f) {
+                f.name.to_token_stream()
             } else {
-                empty_type()
+                self.initial_field_slot(f)
             }
-        }));
-        let init_fields_expr = self.included_fields().map(|f| {
-            f.builder_attr.via_mutators.as_ref().map_or_else(
-                || quote!(()),
-                |via_mutators| {
-                    let init = &via_mutators.init;
-                    quote!((#init,))
-                },
-            )
         });
+
+        // Builder method arguments are the arguments of the fields' setters (renamed per field, so they do not clash), and
+        // the fields are set to the values the setters would have set.
+        let mut builder_method_generics = syn::Generics::default();
+        let mut builder_method_params = Vec::new();
+        let mut builder_method_args_init = Vec::new();
+        for arg in &self.builder_attr.builder_method.args {
+            let field = self.included_fields().find(|f| f.name == arg).expect("args were validated");
+            let SetterSignature { generics, params, value } = field.setter_signature_with_unique_params()?;
+            if let Some(generics) = generics {
+                builder_method_generics.params.extend(generics.params);
+                if let Some(where_clause) = generics.where_clause {
+                    builder_method_generics
+                        .make_where_clause()
+                        .predicates
+                        .extend(where_clause.predicates);
+                }
+            }
+            builder_method_params.push(params);
+            let name = field.name;
+            builder_method_args_init.push(if self.is_step_optional(field) {
+                quote!(let #name = ::core::option::Option::Some(#value);)
+            } else {
+                quote!(let #name = (#value,);)
+            });
+        }
+        let builder_method_where_clause = &builder_method_generics.where_clause;
         let mut all_fields_param_type: syn::TypeParam =
             syn::Ident::new("TypedBuilderFields", proc_macro2::Span::call_site()).into();
         let all_fields_param = syn::GenericParam::Type(all_fields_param_type.clone());
@@ -103,18 +601,40 @@ impl<'a> StructInfo<'a> {
             generics.params.push(syn::GenericParam::Type(all_fields_param_type));
             generics
         };
-        let generics_with_empty = modify_types_generics_hack(&ty_generics, |args| {
+        let generics_with_init = modify_types_generics_hack(&ty_generics, |args| {
             args.push(syn::GenericArgument::Type(init_fields_type.clone().into()));
         });
         let phantom_data = phantom_data_for_generics(self.generics);
 
-        let builder_method_name = self.builder_attr.builder_method.get_name().unwrap_or_else(|| quote!(builder));
+        let init_builder = self.builder_from_slots(
+            &builder_name.clone().into(),
+            init_fields_expr,
+            &init_fields_type,
+            quote!(::core::default::Default::default()),
+        );
+        // With in-place storage, the builder's type parameter only tracks which fields are set.
+        let (fields_type, phantom_data) = if self.in_place() {
+            let storage_name = self.storage_name();
+            (
+                quote!(#storage_name #ty_generics),
+                quote!(::core::marker::PhantomData<(#phantom_data, #all_fields_param)>),
+            )
+        } else {
+            (all_fields_param.to_token_stream(), phantom_data)
+        };
+
+        let builder_method_name = self
+            .builder_attr
+            .builder_method
+            .common
+            .get_name()
+            .unwrap_or_else(|| quote!(builder));
         let builder_method_visibility = first_visibility(&[
-            self.builder_attr.builder_method.vis.as_ref(),
+            self.builder_attr.builder_method.common.vis.as_ref(),
             self.builder_attr.builder_type.common.vis.as_ref(),
             Some(vis),
         ]);
-        let builder_method_doc = self.builder_attr.builder_method.get_doc_or(|| {
+        let builder_method_doc = self.builder_attr.builder_method.common.get_doc_or(|| {
             format!(
                 "
                 Create a builder for building `{name}`.
@@ -133,7 +653,7 @@ impl<'a> StructInfo<'a> {
                         } else {
                             write!(&mut result, ", ").unwrap();
                         }
-                        write!(&mut result, "`.{}(...)`", field.name).unwrap();
+                        write!(&mut result, "`.{}(...)`", field.setter_method_name()).unwrap();
                         if field.builder_attr.default.is_some() {
                             write!(&mut result, "(optional)").unwrap();
                         }
@@ -170,49 +690,107 @@ impl<'a> StructInfo<'a> {
 
         let builder_type_attributes = &self.builder_attr.builder_type.attributes;
 
-        Ok(quote! {
+        let storage_and_clone = if self.in_place() {
+            self.storage_impl()
+        } else {
+            quote! {
+                #[automatically_derived]
+                impl #b_generics_impl Clone for #builder_name #b_generics_ty #b_generics_where {
+                    #[allow(clippy::default_trait_access)]
+                    fn clone(&self) -> Self {
+                        Self {
+                            fields: self.fields.clone(),
+                            phantom: ::core::default::Default::default(),
+                        }
+                    }
+                }
+            }
+        };
+
+        let const_token = self.const_token();
+        let builder_method_impl = self.start_generic_setter_params_at_defaults(quote! {
             #[automatically_derived]
             impl #impl_generics #name #ty_generics #where_clause {
                 #builder_method_doc
                 #[allow(dead_code, clippy::default_trait_access)]
-                #builder_method_visibility fn #builder_method_name() -> #builder_name #generics_with_empty {
-                    #builder_name {
-                        fields: (#(#init_fields_expr,)*),
-                        phantom: ::core::default::Default::default(),
-                    }
+                #builder_method_visibility #const_token fn #builder_method_name #builder_method_generics (#(#builder_method_params),*) -> #builder_name #generics_with_init
+                #builder_method_where_clause
+                {
+                    #(#builder_method_args_init)*
+                    #init_builder
                 }
             }
+        })?;
+
+        Ok(quote! {
+            #builder_method_impl
 
             #[must_use]
             #builder_type_doc
             #[allow(dead_code, non_camel_case_types, non_snake_case)]
             #(#builder_type_attributes)*
             #builder_type_visibility struct #builder_name #b_generics #b_generics_where_extras_predicates {
-                fields: #all_fields_param,
+                fields: #fields_type,
                 phantom: #phantom_data,
             }
 
-            #[automatically_derived]
-            impl #b_generics_impl Clone for #builder_name #b_generics_ty #b_generics_where {
-                #[allow(clippy::default_trait_access)]
-                fn clone(&self) -> Self {
-                    Self {
-                        fields: self.fields.clone(),
-                        phantom: ::core::default::Default::default(),
-                    }
-                }
-            }
+            #storage_and_clone
         })
     }
 
-    fn field_impl(&self, field: &FieldInfo) -> syn::Result<TokenStream> {
+    /// The type parameters that `setter(generic)` setters change start at their defaults, if they have them, so that they
+    /// don't need to be inferred where the builder is created.
+    fn start_generic_setter_params_at_defaults(&self, builder_method_impl: TokenStream) -> syn::Result<TokenStream> {
+        let defaults = self
+            .setter_fields()
+            .filter_map(|f| {
+                let param = f.generic_setter_param()?;
+                let type_param = self.generics.type_params().find(|type_param| type_param.ident == *param)?;
+                Some((param, type_param.default.as_ref()?))
+            })
+            .collect::<Vec<_>>();
+        if defaults.is_empty() {
+            return Ok(builder_method_impl);
+        }
+        let mut item_impl: syn::ItemImpl = syn::parse2(builder_method_impl)?;
+        item_impl.generics.params = item_impl
+            .generics
+            .params
+            .into_iter()
+            .filter(|generic_param| {
+                !matches!(generic_param, syn::GenericParam::Type(type_param) if defaults.iter().any(|(param, _)| type_param.ident == **param))
+            })
+            .collect();
+        for (param, default) in defaults {
+            let mut replace = ReplaceTypeParam { param, with: default };
+            syn::visit_mut::VisitMut::visit_item_impl_mut(&mut replace, &mut item_impl);
+        }
+        Ok(item_impl.into_token_stream())
+    }
+
+    /// The field's setters. In step mode, `state` is the number of step fields that are set in the state of the builder
+    /// the setters are for, and only the methods are returned - without their impl, which they share with the state's
+    /// other setters.
+    fn field_impl(&self, field: &FieldInfo, state: Option<usize>) -> syn::Result<TokenStream> {
         let StructInfo { ref builder_name, .. } = *self;
 
+        // An overridable field's setter is available regardless of whether the field was already set, so the
+        // field's slot is generic like the other fields' and its old value (if any) is discarded. The setter of a field
+        // that presets set also accepts the preset's value. In step mode, the optional fields' setters overwrite their
+        // `Option` slots.
+        let step_optional = self.is_step_optional(field);
+        let overridable = field.builder_attr.setter.overridable.is_some() || step_optional;
+        let preset_overridable = !overridable && field.set_by_preset;
+
         let destructuring = self
             .included_fields()
             .map(|f| {
                 if f.ordinal == field.ordinal {
-                    quote!(())
+                    if overridable || preset_overridable {
+                        quote!(_)
+                    } else {
+                        quote!(())
+                    }
                 } else {
                     let name = f.name;
                     name.to_token_stream()
@@ -226,15 +804,36 @@ impl<'a> StructInfo<'a> {
             ty: field_type,
             ..
         } = field;
+        // A `setter(generic)` setter replaces the struct's type parameter, so the other fields that use it must still be
+        // unset.
+        let generic_param = field.generic_setter_param();
+        let new_generic_param = generic_param.map(|param| format_ident!("__New{}", param));
+        let uses_generic_param =
+            |f: &FieldInfo| f.ordinal != field.ordinal && generic_param.is_some_and(|param| tokens_mention_ident(f.ty, param));
+
         let mut ty_generics = self.generic_arguments();
         let mut target_generics_tuple = empty_type_tuple();
         let mut ty_generics_tuple = empty_type_tuple();
         let generics = {
             let mut generics = self.generics.clone();
             for f in self.included_fields() {
-                if f.ordinal == field.ordinal {
+                if uses_generic_param(f) {
                     ty_generics_tuple.elems.push_value(empty_type());
-                    target_generics_tuple.elems.push_value(f.tuplized_type_ty_param());
+                    target_generics_tuple.elems.push_value(empty_type());
+                } else if f.ordinal == field.ordinal {
+                    if overridable || preset_overridable {
+                        generics.params.push(f.generic_ty_param());
+                        ty_generics_tuple.elems.push_value(f.type_ident());
+                    } else {
+                        ty_generics_tuple.elems.push_value(empty_type());
+                    }
+                    target_generics_tuple
+                        .elems
+                        .push_value(if let Some(new_generic_param) = &new_generic_param {
+                            parse_quote!((#new_generic_param,))
+                        } else {
+                            f.tuplized_type_ty_param()
+                        });
                 } else {
                     generics.params.push(f.generic_ty_param());
                     let generic_argument: syn::Type = f.type_ident();
@@ -247,9 +846,88 @@ impl<'a> StructInfo<'a> {
             generics
         };
         let mut target_generics = ty_generics.clone();
+        if let (Some(param), Some(new_generic_param)) = (generic_param, &new_generic_param) {
+            for argument in &mut target_generics {
+                if matches!(argument, syn::GenericArgument::Type(syn::Type::Path(type_path)) if type_path.path.is_ident(param)) {
+                    *argument = syn::GenericArgument::Type(ident_to_type(new_generic_param.clone()));
+                }
+            }
+        }
         target_generics.push(syn::GenericArgument::Type(target_generics_tuple.into()));
         ty_generics.push(syn::GenericArgument::Type(ty_generics_tuple.into()));
-        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        // In step mode, the setter of a step field moves the builder to the next state, and the setters of the optional
+        // fields keep it in the same state.
+        if let Some(set) = state {
+            let next = if step_optional { set } else { set + 1 };
+            target_generics = self.generic_arguments();
+            target_generics.push(syn::GenericArgument::Type(self.step_state(next).into()));
+        }
+        let where_clause = self.generics.where_clause.as_ref();
+        let mut generics = generics;
+        if preset_overridable {
+            let predicate = self.slot_predicate(field, "PresetOrUnsetSlot");
+            generics.make_where_clause().predicates.push(predicate);
+        }
+        let (impl_generics, _, setter_where_clause) = generics.split_for_impl();
+        // The builder's phantom data changes type along with the type parameter.
+        let phantom = if generic_param.is_some() {
+            quote!(::core::default::Default::default())
+        } else {
+            quote!(self.phantom)
+        };
+        // The slot of the field's value - in-place storage keeps the fields in `Option`s, and so do the slots of the
+        // optional fields of step builders.
+        let to_slot = |value: &TokenStream| {
+            if self.in_place() || step_optional {
+                quote!(::core::option::Option::Some(#value))
+            } else {
+                quote!((#value,))
+            }
+        };
+        // Given the field's slot in a variable named after it, create the builder with the field set. In-place storage only
+        // writes that slot - except in `const` builders, which take the other fields' slots out of the builder instead,
+        // since a `const fn` can neither drop the slot's old value nor the builder.
+        let set_field = if self.in_place() && self.const_token().is_none() {
+            quote! {
+                let mut __self = self;
+                __self.fields.#field_name = #field_name;
+                #builder_name {
+                    fields: __self.fields,
+                    phantom: ::core::marker::PhantomData,
+                }
+            }
+        } else if self.in_place() {
+            let storage_name = self.storage_name();
+            let slots = self.included_fields().map(|f| {
+                let name = f.name;
+                if f.ordinal == field.ordinal {
+                    quote!(#name: #field_name)
+                } else {
+                    quote!(#name: __self.fields.#name.take())
+                }
+            });
+            quote! {
+                let mut __self = self;
+                let __fields = #storage_name {
+                    #(#slots,)*
+                    __phantom: ::core::marker::PhantomData,
+                };
+                #[allow(clippy::forget_non_drop)]
+                ::core::mem::forget(__self);
+                #builder_name {
+                    fields: __fields,
+                    phantom: ::core::marker::PhantomData,
+                }
+            }
+        } else {
+            quote! {
+                let ( #(#destructuring,)* ) = self.fields;
+                #builder_name {
+                    fields: ( #(#reconstructing,)* ),
+                    phantom: #phantom,
+                }
+            }
+        };
         let doc = if let Some(doc) = field.builder_attr.setter.doc.as_ref() {
             Some(quote!(#[doc = #doc]))
         } else if !field.builder_attr.doc_comments.is_empty() {
@@ -267,34 +945,25 @@ impl<'a> StructInfo<'a> {
 
         let deprecated = &field.builder_attr.deprecated;
 
-        let option_was_stripped;
-        let arg_type = if field.builder_attr.setter.strip_option.is_some() && field.builder_attr.setter.transform.is_none() {
-            if let Some(inner_type) = field.type_from_inside_option() {
-                option_was_stripped = true;
-                inner_type
-            } else if field
-                .builder_attr
-                .setter
-                .strip_option
-                .as_ref()
-                .is_some_and(|s| s.ignore_invalid)
-            {
-                option_was_stripped = false;
-                field_type
-            } else {
-                return Err(Error::new_spanned(
-                    field_type,
-                    "can't `strip_option` - field is not `Option<...>`",
-                ));
-            }
+        let SetterSignature {
+            generics: method_generics,
+            params: param_list,
+            value: arg_expr,
+        } = if let (Some(param), Some(new_generic_param)) = (generic_param, &new_generic_param) {
+            self.generic_setter_signature(field, param, new_generic_param)
         } else {
-            option_was_stripped = false;
-            field_type
+            field.setter_signature()?
         };
-        let (arg_type, arg_expr) = if field.builder_attr.setter.auto_into.is_some() {
-            (quote!(impl ::core::convert::Into<#arg_type>), quote!(#field_name.into()))
+        let method_where_clause = method_generics
+            .as_ref()
+            .and_then(|g| g.where_clause.as_ref())
+            .map_or(quote!(), |w| w.to_token_stream());
+        let method_generics = method_generics.as_ref().map_or(quote!(), |g| g.to_token_stream());
+
+        let fallback_arg_expr = if field.builder_attr.setter.auto_into.is_some() {
+            quote!(#field_name.into())
         } else {
-            (arg_type.to_token_stream(), field_name.to_token_stream())
+            quote!(#field_name)
         };
 
         let strip_bool_fallback = field
@@ -303,11 +972,11 @@ impl<'a> StructInfo<'a> {
             .strip_bool
             .as_ref()
             .and_then(|strip_bool| strip_bool.fallback.as_ref())
-            .map(|fallback| (fallback.clone(), quote!(#field_name: #field_type), quote!(#arg_expr)));
+            .map(|fallback| (fallback.clone(), quote!(#field_name: #field_type), fallback_arg_expr.clone()));
 
         let strip_option_fallback = field.builder_attr.setter.strip_option.as_ref().and_then(|strip_option| {
             if let Some(ref fallback) = strip_option.fallback {
-                Some((fallback.clone(), quote!(#field_name: #field_type), quote!(#arg_expr)))
+                Some((fallback.clone(), quote!(#field_name: #field_type), fallback_arg_expr.clone()))
             } else if strip_option.fallback_prefix.is_none() && strip_option.fallback_suffix.is_none() {
                 None
             } else {
@@ -315,36 +984,13 @@ impl<'a> StructInfo<'a> {
                 let prefix = strip_option.fallback_prefix.as_deref().unwrap_or_default();
                 let suffix = strip_option.fallback_suffix.as_deref().unwrap_or_default();
                 let fallback_name = syn::Ident::new(&format!("{}{}{}", prefix, method, suffix), field_name.span());
-                Some((fallback_name, quote!(#field_name: #field_type), quote!(#arg_expr)))
+                Some((fallback_name, quote!(#field_name: #field_type), fallback_arg_expr.clone()))
             }
         });
 
-        let (method_generics, param_list, arg_expr, method_where_clause) = if field.builder_attr.setter.strip_bool.is_some() {
-            (quote!(), quote!(), quote!(true), quote!())
-        } else if let Some(transform) = &field.builder_attr.setter.transform {
-            let params = transform.params.iter().map(|(pat, ty)| quote!(#pat: #ty));
-            let body = &transform.body;
-            let method_generics = transform.generics.as_ref().map_or(quote!(), |g| g.to_token_stream());
-            let method_where_clause = transform
-                .generics
-                .as_ref()
-                .and_then(|g| g.where_clause.as_ref())
-                .map_or(quote!(), |w| w.to_token_stream());
-
-            let body = match &transform.return_type {
-                syn::ReturnType::Default => quote!({ #body }),
-                syn::ReturnType::Type(_, ty) => quote!({
-                    let value: #ty = { #body };
-                    value
-                }),
-            };
-
-            (method_generics, quote!(#(#params),*), body, method_where_clause)
-        } else if option_was_stripped {
-            (quote!(), quote!(#field_name: #arg_type), quote!(Some(#arg_expr)), quote!())
-        } else {
-            (quote!(), quote!(#field_name: #arg_type), arg_expr, quote!())
-        };
+        let method_name = field.setter_method_name();
+        let setter_vis = field.setter_vis();
+        let const_token = self.const_token();
 
         let repeated_fields_error_type_name = syn::Ident::new(
             &format!(
@@ -355,23 +1001,67 @@ impl<'a> StructInfo<'a> {
             proc_macro2::Span::call_site(),
         );
         let repeated_fields_error_message = format!("Repeated field {}", field_name);
-
-        let method_name = field.setter_method_name();
+        // The target type of a `setter(generic)` setter uses the setter's own type parameter - and the setter of a field
+        // that presets set takes its slot as a type parameter - so the error is implemented for the builder with the field
+        // set and everything else generic.
+        let (repeated_impl_generics, repeated_target_generics) = if generic_param.is_some() || preset_overridable {
+            let mut generics = self.generics.clone();
+            let mut fields_tuple = empty_type_tuple();
+            for f in self.included_fields() {
+                if f.ordinal == field.ordinal {
+                    fields_tuple.elems.push(f.tuplized_type_ty_param());
+                } else {
+                    generics.params.push(f.generic_ty_param());
+                    fields_tuple.elems.push(f.type_ident());
+                }
+                fields_tuple.elems.push_punct(Default::default());
+            }
+            let mut target_generics = self.generic_arguments();
+            target_generics.push(syn::GenericArgument::Type(fields_tuple.into()));
+            let (impl_generics, _, _) = generics.split_for_impl();
+            (impl_generics.to_token_stream(), target_generics)
+        } else {
+            (impl_generics.to_token_stream(), target_generics.clone())
+        };
+        let method_names = core::iter::once(&method_name).chain(&field.builder_attr.setter.aliases);
+        let repeated_fields_error = if overridable {
+            None
+        } else {
+            Some(quote! {
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, non_snake_case)]
+                #[allow(clippy::exhaustive_enums)]
+                pub enum #repeated_fields_error_type_name {}
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, missing_docs)]
+                #[automatically_derived]
+                impl #repeated_impl_generics #builder_name <#repeated_target_generics> #where_clause {
+                    #(
+                        #[deprecated(
+                            note = #repeated_fields_error_message
+                        )]
+                        #doc
+                        #setter_vis #const_token fn #method_names #method_generics (self, _: #repeated_fields_error_type_name) -> #builder_name <#repeated_target_generics>
+                        #method_where_clause
+                        {
+                            self
+                        }
+                    )*
+                }
+            })
+        };
 
         let strip_option_fallback_method = if let Some((method_name, param_list, arg_expr)) = strip_option_fallback {
+            let slot = to_slot(&arg_expr);
             Some(quote! {
                 #deprecated
                 #doc
                 #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
-                pub fn #method_name #method_generics (self, #param_list) -> #builder_name <#target_generics>
+                #setter_vis #const_token fn #method_name #method_generics (self, #param_list) -> #builder_name <#target_generics>
                 #method_where_clause
                 {
-                    let #field_name = (#arg_expr,);
-                    let ( #(#destructuring,)* ) = self.fields;
-                    #builder_name {
-                        fields: ( #(#reconstructing,)* ),
-                        phantom: self.phantom,
-                    }
+                    let #field_name = #slot;
+                    #set_field
                 }
             })
         } else {
@@ -379,64 +1069,307 @@ impl<'a> StructInfo<'a> {
         };
 
         let strip_bool_fallback_method = if let Some((method_name, param_list, arg_expr)) = strip_bool_fallback {
+            let slot = to_slot(&arg_expr);
             Some(quote! {
                 #deprecated
                 #doc
                 #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
-                pub fn #method_name #method_generics (self, #param_list) -> #builder_name <#target_generics>
+                #setter_vis #const_token fn #method_name #method_generics (self, #param_list) -> #builder_name <#target_generics>
                 #method_where_clause
                 {
-                    let #field_name = (#arg_expr,);
-                    let ( #(#destructuring,)* ) = self.fields;
-                    #builder_name {
-                        fields: ( #(#reconstructing,)* ),
-                        phantom: self.phantom,
-                    }
+                    let #field_name = #slot;
+                    #set_field
                 }
             })
         } else {
             None
         };
 
-        Ok(quote! {
-            #[allow(dead_code, non_camel_case_types, missing_docs)]
-            #[automatically_derived]
-            impl #impl_generics #builder_name <#ty_generics> #where_clause {
-                #deprecated
+        let strip_bool_negated_method = field
+            .builder_attr
+            .setter
+            .strip_bool
+            .as_ref()
+            .and_then(|strip_bool| strip_bool.negated.as_ref())
+            .map(|method_name| {
+                let doc = format!("Set `{}` to `false`.", field_name);
+                let slot = to_slot(&quote!(false));
+                quote! {
+                    #deprecated
+                    #[doc = #doc]
+                    #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                    #setter_vis #const_token fn #method_name (self) -> #builder_name <#target_generics> {
+                        let #field_name = #slot;
+                        #set_field
+                    }
+                }
+            });
+
+        let slot = to_slot(&arg_expr);
+        let alias_methods = field.builder_attr.setter.aliases.iter().map(|alias| {
+            let note = format!("Use `{}` instead", method_name);
+            quote! {
+                #[deprecated(note = #note)]
                 #doc
                 #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
-                pub fn #method_name #method_generics (self, #param_list) -> #builder_name <#target_generics>
+                #setter_vis #const_token fn #alias #method_generics (self, #param_list) -> #builder_name <#target_generics>
                 #method_where_clause
                 {
-                    let #field_name = (#arg_expr,);
-                    let ( #(#destructuring,)* ) = self.fields;
-                    #builder_name {
-                        fields: ( #(#reconstructing,)* ),
-                        phantom: self.phantom,
+                    let #field_name = #slot;
+                    #set_field
+                }
+            }
+        });
+
+        let methods = quote! {
+            #deprecated
+            #doc
+            #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+            #setter_vis #const_token fn #method_name #method_generics (self, #param_list) -> #builder_name <#target_generics>
+            #method_where_clause
+            {
+                let #field_name = #slot;
+                #set_field
+            }
+            #(#alias_methods)*
+            #strip_option_fallback_method
+            #strip_bool_fallback_method
+            #strip_bool_negated_method
+        };
+        if state.is_some() {
+            return Ok(methods);
+        }
+        Ok(quote! {
+            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[automatically_derived]
+            impl #impl_generics #builder_name <#ty_generics> #setter_where_clause {
+                #methods
+            }
+            #repeated_fields_error
+        })
+    }
+
+    /// In step mode - the builder's setters, in one impl per state. Each state is a concrete type, in which the first
+    /// step fields are `(T,)`, the others `()` and the optional fields `Option<T>`. Its impl has the setter of the next
+    /// step field, which moves the builder to the next state, and the setters and reset methods of the optional fields,
+    /// which keep it in the same state.
+    fn step_impls(&self) -> syn::Result<TokenStream> {
+        let StructInfo { ref builder_name, .. } = *self;
+        let step_fields = self.step_fields().collect::<Vec<_>>();
+        let (impl_generics, _, where_clause) = self.generics.split_for_impl();
+        (0..=step_fields.len())
+            .map(|set| {
+                let step_setter = step_fields.get(set).map(|f| self.field_impl(f, Some(set))).transpose()?;
+                let optional_setters = self
+                    .setter_fields()
+                    .filter(|f| self.is_step_optional(f))
+                    .map(|f| self.field_impl(f, Some(set)))
+                    .collect::<syn::Result<Vec<_>>>()?;
+                let resets = self
+                    .setter_fields()
+                    .filter(|f| self.is_step_optional(f) && f.builder_attr.setter.resettable.is_some())
+                    .map(|f| self.reset_impl(f, Some(set)));
+                let mut ty_generics = self.generic_arguments();
+                ty_generics.push(syn::GenericArgument::Type(self.step_state(set).into()));
+                Ok(quote! {
+                    #[allow(dead_code, non_camel_case_types, missing_docs)]
+                    #[automatically_derived]
+                    impl #impl_generics #builder_name <#ty_generics> #where_clause {
+                        #step_setter
+                        #(#optional_setters)*
+                        #(#resets)*
                     }
+                })
+            })
+            .collect()
+    }
+
+    /// The signature of a `setter(generic)` setter - it takes the field's value as a new type parameter, which has the
+    /// bounds the struct puts on the type parameter it replaces.
+    fn generic_setter_signature(&self, field: &FieldInfo, param: &syn::Ident, new_param: &syn::Ident) -> SetterSignature {
+        let new_type = ident_to_type(new_param.clone());
+        let mut replace = ReplaceTypeParam { param, with: &new_type };
+        let inline_bounds = self.generics.type_params().filter(|p| !p.bounds.is_empty()).map(|p| {
+            let syn::TypeParam { ident, bounds, .. } = p;
+            parse_quote!(#ident: #bounds)
+        });
+        let where_predicates = self.generics.where_clause.iter().flat_map(|w| w.predicates.iter().cloned());
+        let mut generics: syn::Generics = parse_quote!(<#new_param>);
+        for mut predicate in inline_bounds.chain(where_predicates) {
+            if tokens_mention_ident(&predicate, param) {
+                syn::visit_mut::VisitMut::visit_where_predicate_mut(&mut replace, &mut predicate);
+                generics.make_where_clause().predicates.push(predicate);
+            }
+        }
+        let field_name = field.name;
+        SetterSignature {
+            generics: Some(generics),
+            params: quote!(#field_name: #new_param),
+            value: quote!(#field_name),
+        }
+    }
+
+    /// The field's reset method. In step mode, the method empties the field's `Option` slot and is returned without its
+    /// impl, like in [`Self::field_impl`].
+    fn reset_impl(&self, field: &FieldInfo, state: Option<usize>) -> TokenStream {
+        let StructInfo { ref builder_name, .. } = *self;
+
+        let destructuring = self.included_fields().map(|f| {
+            if f.ordinal == field.ordinal {
+                quote!(_)
+            } else {
+                f.name.to_token_stream()
+            }
+        });
+        let reconstructing = self.included_fields().map(|f| {
+            if f.ordinal != field.ordinal {
+                f.name.to_token_stream()
+            } else if state.is_some() {
+                quote!(::core::option::Option::None)
+            } else {
+                quote!(())
+            }
+        });
+
+        let mut generics = self.generics.clone();
+        let mut ty_generics = self.generic_arguments();
+        let mut target_generics = ty_generics.clone();
+        let mut ty_generics_tuple = empty_type_tuple();
+        let mut target_generics_tuple = empty_type_tuple();
+        for f in self.included_fields() {
+            if f.builder_attr.via_mutators.is_some() {
+                ty_generics_tuple.elems.push(f.tuplized_type_ty_param());
+                target_generics_tuple.elems.push(f.tuplized_type_ty_param());
+            } else {
+                generics.params.push(f.generic_ty_param());
+                ty_generics_tuple.elems.push(f.type_ident());
+                target_generics_tuple.elems.push(if f.ordinal == field.ordinal {
+                    empty_type()
+                } else {
+                    f.type_ident()
+                });
+            }
+            ty_generics_tuple.elems.push_punct(Default::default());
+            target_generics_tuple.elems.push_punct(Default::default());
+        }
+        ty_generics.push(syn::GenericArgument::Type(ty_generics_tuple.into()));
+        target_generics.push(syn::GenericArgument::Type(target_generics_tuple.into()));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        let deprecated = &field.builder_attr.deprecated;
+        let method_name = field.reset_method_name();
+        let setter_vis = field.setter_vis();
+        let doc = format!("Unset `{}`, so that it can be set again or left to its default.", field.name);
+        // In step mode, unsetting an optional field keeps the builder in the same state.
+        if let Some(set) = state {
+            target_generics = self.generic_arguments();
+            target_generics.push(syn::GenericArgument::Type(self.step_state(set).into()));
+        }
+        let body = if self.in_place() {
+            let field_name = field.name;
+            quote! {
+                let mut __self = self;
+                __self.fields.#field_name = ::core::option::Option::None;
+                #builder_name {
+                    fields: __self.fields,
+                    phantom: ::core::marker::PhantomData,
                 }
-                #strip_option_fallback_method
-                #strip_bool_fallback_method
             }
-            #[doc(hidden)]
-            #[allow(dead_code, non_camel_case_types, non_snake_case)]
-            #[allow(clippy::exhaustive_enums)]
-            pub enum #repeated_fields_error_type_name {}
-            #[doc(hidden)]
+        } else {
+            quote! {
+                let ( #(#destructuring,)* ) = self.fields;
+                #builder_name {
+                    fields: ( #(#reconstructing,)* ),
+                    phantom: self.phantom,
+                }
+            }
+        };
+
+        let method = quote! {
+            #deprecated
+            #[doc = #doc]
+            #[allow(clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+            #setter_vis fn #method_name (self) -> #builder_name <#target_generics> {
+                #body
+            }
+        };
+        if state.is_some() {
+            return method;
+        }
+        quote! {
             #[allow(dead_code, non_camel_case_types, missing_docs)]
             #[automatically_derived]
-            impl #impl_generics #builder_name <#target_generics> #where_clause {
-                #[deprecated(
-                    note = #repeated_fields_error_message
-                )]
-                #doc
-                pub fn #method_name #method_generics (self, _: #repeated_fields_error_type_name) -> #builder_name <#target_generics>
-                #method_where_clause
-                {
-                    self
+            impl #impl_generics #builder_name <#ty_generics> #where_clause {
+                #method
+            }
+        }
+    }
+
+    fn getters_impl(&self, field: &FieldInfo) -> TokenStream {
+        let StructInfo { ref builder_name, .. } = *self;
+
+        let mut generics = self.generics.clone();
+        let mut ty_generics = self.generic_arguments();
+        let mut ty_generics_tuple = empty_type_tuple();
+        let mut field_index = None;
+        for (i, f) in self.included_fields().enumerate() {
+            if f.ordinal == field.ordinal {
+                field_index = Some(syn::Index::from(i));
+                ty_generics_tuple.elems.push(self.set_field_slot(f, &mut generics));
+            } else {
+                generics.params.push(f.generic_ty_param());
+                ty_generics_tuple.elems.push(f.type_ident());
+            }
+            ty_generics_tuple.elems.push_punct(Default::default());
+        }
+        ty_generics.push(syn::GenericArgument::Type(ty_generics_tuple.into()));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let field_index = field_index.expect("getters are only generated for included fields");
+
+        let field_type = field.ty;
+        let field_type_ident = field.type_ident();
+        let deprecated = &field.builder_attr.deprecated;
+        let (getter_name, getter_mut_name) = field.getter_method_names();
+        let setter_vis = field.setter_vis();
+        let (getter, getter_mut) = if self.in_place() {
+            let crate_module_path = &self.builder_attr.crate_module_path;
+            let field_name = field.name;
+            (
+                quote!(#crate_module_path::__private::set_slot(self.fields.#field_name.as_ref())),
+                quote!(#crate_module_path::__private::set_slot(self.fields.#field_name.as_mut())),
+            )
+        } else if field.set_by_preset {
+            let StructInfo { name, .. } = self;
+            let (_, struct_ty_generics, _) = self.generics.split_for_impl();
+            let crate_module_path = &self.builder_attr.crate_module_path;
+            let slot_trait =
+                quote!(<#field_type_ident as #crate_module_path::__private::SetSlot<#name #struct_ty_generics, #field_type>>);
+            (
+                quote!(#slot_trait::get(&self.fields.#field_index)),
+                quote!(#slot_trait::get_mut(&mut self.fields.#field_index)),
+            )
+        } else {
+            (quote!(&self.fields.#field_index.0), quote!(&mut self.fields.#field_index.0))
+        };
+        let getter_doc = format!("Get a reference to the already set `{}`.", field.name);
+        let getter_mut_doc = format!("Get a mutable reference to the already set `{}`.", field.name);
+
+        quote! {
+            #[allow(dead_code, non_camel_case_types, missing_docs)]
+            #[automatically_derived]
+            impl #impl_generics #builder_name <#ty_generics> #where_clause {
+                #deprecated
+                #[doc = #getter_doc]
+                #setter_vis fn #getter_name(&self) -> &#field_type {
+                    #getter
+                }
+                #deprecated
+                #[doc = #getter_mut_doc]
+                #setter_vis fn #getter_mut_name(&mut self) -> &mut #field_type {
+                    #getter_mut
                 }
             }
-        })
+        }
     }
 
     fn required_field_impl(&self, field: &FieldInfo) -> TokenStream {
@@ -476,7 +1409,8 @@ impl<'a> StructInfo<'a> {
                 } else if f.ordinal < field.ordinal {
                     // Only add a `build` method that warns about missing `field` if `f` is set. If `f` is not set,
                     // `f`'s `build` method will warn, since it appears earlier in the argument list.
-                    builder_generics_tuple.elems.push_value(f.tuplized_type_ty_param());
+                    let slot = self.set_field_slot(f, &mut generics);
+                    builder_generics_tuple.elems.push_value(slot);
                 } else if f.ordinal == field.ordinal {
                     builder_generics_tuple.elems.push_value(empty_type());
                 } else {
@@ -493,6 +1427,14 @@ impl<'a> StructInfo<'a> {
         };
 
         builder_generics.push(syn::GenericArgument::Type(builder_generics_tuple.into()));
+        // In step mode, the required fields are set in order, so the error is for the one state in which `field` is next.
+        let generics = if let Some(set) = self.step_fields().position(|f| f.ordinal == field.ordinal) {
+            builder_generics = self.generic_arguments().into_iter().collect();
+            builder_generics.push(syn::GenericArgument::Type(self.step_state(set).into()));
+            self.generics.clone()
+        } else {
+            generics
+        };
         let (impl_generics, _, where_clause) = generics.split_for_impl();
 
         let early_build_error_type_name = syn::Ident::new(
@@ -532,43 +1474,159 @@ impl<'a> StructInfo<'a> {
         mutator @ Mutator {
             fun: mutator_fn,
             required_fields,
+            set_fields,
         }: &Mutator,
+        builder_name: &syn::Path,
+        external: bool,
     ) -> syn::Result<TokenStream> {
-        let StructInfo { ref builder_name, .. } = *self;
-
         let mut required_fields = required_fields.clone();
+        let mut unknown_set_fields = set_fields.clone();
 
         let mut ty_generics = self.generic_arguments();
-        let mut destructuring = TokenStream::new();
+        let mut target_generics = ty_generics.clone();
+        let mut destructuring = Vec::new();
+        let mut reconstructing = Vec::new();
         let mut ty_generics_tuple = empty_type_tuple();
+        let mut target_generics_tuple = empty_type_tuple();
         let mut generics = self.generics.clone();
         let mut mutator_ty_fields = Punctuated::<_, Token![,]>::new();
+        let mut mutator_init_fields = Punctuated::<_, Token![,]>::new();
         let mut mutator_destructure_fields = Punctuated::<_, Token![,]>::new();
+        // With in-place storage, the fields the mutator uses are taken out of their slots and written back.
+        let mut taken_fields = Vec::new();
+        let mut written_fields = Vec::new();
+        let fn_name = &mutator_fn.sig.ident;
         for f @ FieldInfo { name, ty, .. } in self.included_fields() {
-            if f.builder_attr.via_mutators.is_some() || required_fields.remove(f.name) {
-                ty_generics_tuple.elems.push(f.tuplized_type_ty_param());
+            if let Some(index) = unknown_set_fields.iter().position(|set_field| set_field == f.name) {
+                let set_field = unknown_set_fields.remove(index);
+                if f.builder_attr.via_mutators.is_some() {
+                    return Err(Error::new_spanned(
+                        set_field,
+                        format!("`{}` is `via_mutators`, so it is always set", name),
+                    ));
+                }
+                ty_generics_tuple.elems.push(empty_type());
+                target_generics_tuple.elems.push(f.tuplized_type_ty_param());
+                destructuring.push(quote!(()));
+                reconstructing.push(quote!((#name,)));
+                written_fields.push(name);
+            } else if f.builder_attr.via_mutators.is_some() || required_fields.remove(f.name) {
+                ty_generics_tuple.elems.push(self.set_field_slot(f, &mut generics));
+                target_generics_tuple.elems.push(f.tuplized_type_ty_param());
                 mutator_ty_fields.push(quote!(#name: #ty));
+                taken_fields.push(name);
+                written_fields.push(name);
+                if f.set_by_preset && !self.in_place() {
+                    let value = self.set_field_value(f, quote!(#name));
+                    mutator_init_fields.push(quote!(#name: #value));
+                    destructuring.push(quote!(#name));
+                } else {
+                    mutator_init_fields.push(quote!(#name));
+                    destructuring.push(quote!((#name,)));
+                }
                 mutator_destructure_fields.push(name);
-                quote!((#name,),).to_tokens(&mut destructuring);
+                reconstructing.push(quote!((#name,)));
             } else {
                 generics.params.push(f.generic_ty_param());
                 let generic_argument: syn::Type = f.type_ident();
                 ty_generics_tuple.elems.push(generic_argument.clone());
-                quote!(#name,).to_tokens(&mut destructuring);
+                target_generics_tuple.elems.push(generic_argument);
+                destructuring.push(quote!(#name));
+                reconstructing.push(quote!(#name));
             }
         }
-        ty_generics.push(syn::GenericArgument::Type(ty_generics_tuple.into()));
+        if let Some(set_field) = unknown_set_fields.into_iter().next() {
+            return Err(Error::new_spanned(
+                &set_field,
+                format!("`{}` is not a field that can be set", set_field),
+            ));
+        }
+        ty_generics.push(syn::GenericArgument::Type(ty_generics_tuple.clone().into()));
+        target_generics.push(syn::GenericArgument::Type(target_generics_tuple.clone().into()));
+        // Mutators defined outside the derive cannot access the builder's private fields.
+        let (take_fields, builder) = if external {
+            (
+                quote!(let ( #(#destructuring,)* ) = self.__into_typed_builder_fields();),
+                quote!(#builder_name::<#target_generics>::__from_typed_builder_fields((#(#reconstructing,)*))),
+            )
+        } else if self.in_place() {
+            let crate_module_path = &self.builder_attr.crate_module_path;
+            (
+                quote! {
+                    let mut __self = self;
+                    #(let #taken_fields = #crate_module_path::__private::set_slot(__self.fields.#taken_fields.take());)*
+                },
+                quote! {{
+                    #(__self.fields.#written_fields = ::core::option::Option::Some(#written_fields);)*
+                    #builder_name::<#target_generics> {
+                        fields: __self.fields,
+                        phantom: ::core::marker::PhantomData,
+                    }
+                }},
+            )
+        } else {
+            (
+                quote!(let ( #(#destructuring,)* ) = self.fields;),
+                self.builder_from_slots(builder_name, reconstructing, &target_generics_tuple, quote!(self.phantom)),
+            )
+        };
         let (impl_generics, _, where_clause) = generics.split_for_impl();
 
         let mutator_struct_name = format_ident!("TypedBuilderFieldMutator");
 
         let ItemFn { attrs, vis, .. } = mutator_fn;
-        let sig = mutator.outer_sig(parse_quote!(#builder_name <#ty_generics>));
-        let fn_name = &sig.ident;
+        let sig = mutator.outer_sig(parse_quote!(#builder_name <#target_generics>));
         let (_fn_impl_generics, fn_ty_generics, _fn_where_clause) = &sig.generics.split_for_impl();
         let fn_call_turbofish = fn_ty_generics.as_turbofish();
         let mutator_args = mutator.arguments();
 
+        let call = quote! {
+            let (#mutator_args) = __args;
+            __mutator.#fn_name #fn_call_turbofish(#mutator_args)
+        };
+        // The mutator returns the values of the `sets` fields, so the compiler makes sure it sets all of them.
+        // The field names are used rather than the idents of the `sets` list, since they name the same bindings as
+        // the reconstructed builder.
+        let set_field_names = set_fields
+            .iter()
+            .filter_map(|set_field| self.included_fields().find(|f| f.name == set_field).map(|f| f.name))
+            .collect::<Vec<_>>();
+        let set_values = match set_field_names.as_slice() {
+            [field] => quote!(#field),
+            fields => quote!((#(#fields),*)),
+        };
+        let (call, output) = match mutator.output() {
+            MutatorOutput::Builder => (quote!({ #call; }), quote!(__builder)),
+            MutatorOutput::WithValue => (quote!(let __value = { #call };), quote!((__builder, __value))),
+            MutatorOutput::Fallible => (
+                quote! {
+                    if let ::core::result::Result::Err(error) = { #call } {
+                        return ::core::result::Result::Err(error);
+                    }
+                },
+                quote!(::core::result::Result::Ok(__builder)),
+            ),
+            MutatorOutput::FallibleWithValue => (
+                quote! {
+                    let __value = match { #call } {
+                        ::core::result::Result::Ok(value) => value,
+                        ::core::result::Result::Err(error) => return ::core::result::Result::Err(error),
+                    };
+                },
+                quote!(::core::result::Result::Ok((__builder, __value))),
+            ),
+            MutatorOutput::SetsFields => (quote!(let #set_values = { #call };), quote!(__builder)),
+            MutatorOutput::FallibleSetsFields => (
+                quote! {
+                    let #set_values = match { #call } {
+                        ::core::result::Result::Ok(values) => values,
+                        ::core::result::Result::Err(error) => return ::core::result::Result::Err(error),
+                    };
+                },
+                quote!(::core::result::Result::Ok(__builder)),
+            ),
+        };
+
         // Generics for the mutator - should be similar to the struct's generics
         let m_generics = &self.generics;
         let (m_impl_generics, m_ty_generics, m_where_clause) = m_generics.split_for_impl();
@@ -585,33 +1643,30 @@ impl<'a> StructInfo<'a> {
                         __phantom: #m_phantom,
                         #mutator_ty_fields
                     }
+                    // Mutators must take `&mut self`, even those that only return the values of the fields they set.
+                    #[allow(clippy::unused_self)]
                     impl #m_impl_generics #mutator_struct_name #m_ty_generics #m_where_clause {
                         #mutator_fn
                     }
 
                     let __args = (#mutator_args);
 
-                    let ( #destructuring ) = self.fields;
+                    #take_fields
                     let mut __mutator: #mutator_struct_name #m_ty_generics = #mutator_struct_name {
                         __phantom: ::core::default::Default::default(),
-                        #mutator_destructure_fields
+                        #mutator_init_fields
                     };
 
                     // This dance is required to keep mutator args and destrucutre fields from interfering.
-                    {
-                        let (#mutator_args) = __args;
-                        __mutator.#fn_name #fn_call_turbofish(#mutator_args);
-                    }
+                    #call
 
                     let #mutator_struct_name {
                         __phantom,
                         #mutator_destructure_fields
                     } = __mutator;
 
-                    #builder_name {
-                        fields: ( #destructuring ),
-                        phantom: self.phantom,
-                    }
+                    let __builder = #builder;
+                    #output
                 }
             }
         })
@@ -625,94 +1680,120 @@ impl<'a> StructInfo<'a> {
         first_visibility(&[self.builder_attr.build_method.common.vis.as_ref(), Some(&public_visibility())])
     }
 
-    fn build_method_impl(&self) -> TokenStream {
-        let StructInfo {
-            ref name,
-            ref builder_name,
-            ..
-        } = *self;
-
-        let generics = {
-            let mut generics = self.generics.clone();
-            for field in self.included_fields() {
-                if field.builder_attr.default.is_some() {
-                    let generic_param: syn::TypeParam = field.generic_ident.clone().into();
-                    generics.params.push(generic_param.into());
-                }
-            }
-            generics
-        };
-        let (impl_generics, _, _) = generics.split_for_impl();
-
-        let (_, ty_generics, where_clause) = self.generics.split_for_impl();
-
-        let name_with_generics = quote!(#name #ty_generics);
-
-        let modified_ty_generics = modify_types_generics_hack(&ty_generics, |args| {
-            args.push(syn::GenericArgument::Type(
-                type_tuple(self.included_fields().map(|field| {
-                    if field.builder_attr.default.is_some() {
-                        field.type_ident()
-                    } else {
-                        field.tuplized_type_ty_param()
-                    }
-                }))
-                .into(),
-            ));
-        });
-
-        let destructuring = self.included_fields().map(|f| f.name);
+    /// The struct's where clause, extended with more predicates.
+    fn where_clause_with(&self, extra_predicates: Vec<syn::WherePredicate>) -> Option<syn::WhereClause> {
+        if extra_predicates.is_empty() {
+            return None;
+        }
+        let mut predicates: Punctuated<_, _> = Default::default();
+        if let Some(where_clause) = &self.generics.where_clause {
+            predicates.extend(where_clause.predicates.iter().cloned());
+        }
+        predicates.extend(extra_predicates);
+        Some(syn::WhereClause {
+            where_token: Default::default(),
+            predicates,
+        })
+    }
 
+    /// The bounds needed for resolving the defaults of the fields with [`Self::default_resolution`].
+    fn default_resolution_predicates(&self, values: FieldValues) -> Vec<syn::WherePredicate> {
+        let StructInfo { name, .. } = self;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
         let crate_module_path = &self.builder_attr.crate_module_path;
 
-        let where_predicates_for_defaults: Vec<syn::WherePredicate> = self.fields.iter().enumerate()
-            .filter(|(_, field)| field.builder_attr.default.is_some() && field.builder_attr.setter.skip.is_none())
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| {
+                field.builder_attr.default.is_some()
+                    && field.builder_attr.setter.skip.is_none()
+                    && (matches!(values, FieldValues::BuilderState) || field.builder_attr.via_mutators.is_none())
+            })
+            // Outside of the build method, only the defaults with a `default_where` are resolved with `NextFieldDefault`, so
+            // that their bounds are only required when they are used.
+            .filter(|(_, field)| matches!(values, FieldValues::BuilderState) || field.builder_attr.default_where.is_some())
             .map(|(field_index, field)| {
-                let types = self.fields.iter().take(field_index).map(|dep_field| {
-                    let dep_type = dep_field.ty;
-                    let dep_mut = dep_field.maybe_mut();
-                    quote!(&'__typed_builder_lifetime_for_default #dep_mut #dep_type)
-                }).chain(core::iter::once({
-                    let generic_argument: syn::Type = field.type_ident();
-                    quote!(#generic_argument)
-                }));
+                let types = self
+                    .fields
+                    .iter()
+                    .take(field_index)
+                    .map(|dep_field| {
+                        let dep_type = dep_field.ty;
+                        let dep_mut = dep_field.maybe_mut();
+                        quote!(&'__typed_builder_lifetime_for_default #dep_mut #dep_type)
+                    })
+                    .chain(core::iter::once(match values {
+                        FieldValues::BuilderState => field.type_ident().to_token_stream(),
+                        FieldValues::Optional | FieldValues::Storage | FieldValues::RequiredOnly => quote!(()),
+                    }));
                 let field_type = field.ty;
-                parse_quote!{
-                    #name_with_generics: for<'__typed_builder_lifetime_for_default> #crate_module_path::NextFieldDefault<(#(#types,)*), Output = #field_type>
+                parse_quote! {
+                    #name #ty_generics: for<'__typed_builder_lifetime_for_default> #crate_module_path::NextFieldDefault<(#(#types,)*), Output = #field_type>
                 }
+            })
+            .chain(
+                // The required fields that presets set may be in their `Preset` slots.
+                self.included_fields()
+                    .filter(|field| {
+                        matches!(values, FieldValues::BuilderState | FieldValues::Storage)
+                            && field.set_by_preset
+                            && field.builder_attr.default.is_none()
+                    })
+                    .map(|field| self.slot_predicate(field, "SetSlot")),
+            )
+            .collect()
+    }
 
-            }).collect::<Vec<_>>();
-        let where_clause_storage;
-        let where_clause = if where_predicates_for_defaults.is_empty() {
-            where_clause
-        } else {
-            let mut predicates: Punctuated<_, _> = Default::default();
-            if let Some(where_clause) = where_clause {
-                predicates.extend(where_clause.predicates.iter().cloned());
-            }
-            predicates.extend(where_predicates_for_defaults);
-            where_clause_storage = syn::WhereClause {
-                where_token: Default::default(),
-                predicates,
-            };
-            Some(&where_clause_storage)
-        };
+    /// Statements that shadow the variable of each field with the field's final value, resolving the defaults of the
+    /// fields that were not set.
+    ///
+    /// In [`FieldValues::Optional`] mode, the required fields must already be unwrapped with
+    /// [`Self::unwrap_required_fields`].
+    fn default_resolution(&self, values: FieldValues) -> Vec<TokenStream> {
+        let StructInfo { name, .. } = self;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let name_with_generics = quote!(#name #ty_generics);
+        let crate_module_path = &self.builder_attr.crate_module_path;
 
         // The default of a field can refer to earlier-defined fields, which we handle by
         // writing out a bunch of `let` statements first, which can each refer to earlier ones.
         // This means that field ordering may actually be significant, which isn't ideal. We could
         // relax that restriction by calculating a DAG of field default dependencies and
         // reordering based on that, but for now this much simpler thing is a reasonable approach.
-        let assignments = self
-            .fields
+        self.fields
             .iter()
             .enumerate()
             .map(|(field_index, field)| {
                 let name = &field.name;
                 let maybe_mut = field.maybe_mut();
 
-                if let Some(ref default) = field.builder_attr.default {
-                    if field.builder_attr.setter.skip.is_some() {
+                if let (FieldValues::Optional, Some(via_mutators)) = (values, &field.builder_attr.via_mutators) {
+                    let init = &via_mutators.init;
+                    quote! {
+                        let #maybe_mut #name = match #name {
+                            ::core::option::Option::Some(#name) => #name,
+                            ::core::option::Option::None => #init,
+                        };
+                    }
+                } else if let (FieldValues::RequiredOnly, Some(via_mutators)) = (values, &field.builder_attr.via_mutators) {
+                    let init = &via_mutators.init;
+                    quote!(let #maybe_mut #name = #init;)
+                } else if let Some(ref default) = field.builder_attr.default {
+                    let (types, values_of_deps): (Vec<_>, Vec<_>) = self
+                        .fields
+                        .iter()
+                        .take(field_index)
+                        .map(|dep_field| {
+                            let dep_type = dep_field.ty;
+                            let dep_name = dep_field.name;
+                            let dep_mut = dep_field.maybe_mut();
+                            (quote!(&#dep_mut #dep_type), quote!(&#dep_mut #dep_name))
+                        })
+                        .unzip();
+                    // The build method resolves all the defaults with `NextFieldDefault`. Elsewhere, only the defaults
+                    // with a `default_where` need it - the others are written inline.
+                    let default = if field.builder_attr.setter.skip.is_some() || field.builder_attr.default_where.is_none() {
                         let make_fields_refs = self.fields.iter().take(field_index).map(|dep_field| {
                             let dep_name = dep_field.name;
                             let dep_mut = dep_field.maybe_mut();
@@ -721,57 +1802,188 @@ impl<'a> StructInfo<'a> {
                                 let #dep_name = &#dep_mut #dep_name;
                             }
                         });
-                        quote! {
-                            let #maybe_mut #name = {
-                                #(#make_fields_refs)*
-                                #default
-                            };
-                        }
+                        quote! {{
+                            #(#make_fields_refs)*
+                            #default
+                        }}
                     } else {
-                        let (types, values): (Vec<_>, Vec<_>) = self
-                            .fields
-                            .iter()
-                            .take(field_index)
-                            .map(|dep_field| {
-                                let dep_type = dep_field.ty;
-                                let dep_name = dep_field.name;
-                                let dep_mut = dep_field.maybe_mut();
-                                (quote!(&#dep_mut #dep_type), quote!(&#dep_mut #dep_name))
-                            })
-                            .chain(core::iter::once({
-                                let generic_argument: syn::Type = field.type_ident();
-                                (quote!(#generic_argument), quote!(#name))
-                            }))
-                            .unzip();
-
                         quote! {
-                            let #maybe_mut #name = <
+                            <
                                 #name_with_generics
                                 as
-                                #crate_module_path::NextFieldDefault<(#(#types,)*)>>::resolve((#(#values,)*));
+                                #crate_module_path::NextFieldDefault<(#(#types,)* (),)>
+                            >::resolve((#(#values_of_deps,)* (),))
                         }
+                    };
+                    if field.builder_attr.setter.skip.is_some() {
+                        return quote!(let #maybe_mut #name = #default;);
+                    }
+                    match values {
+                        // The trait's type parameter is inferred from the argument, so that the build method does not
+                        // spell out the types of all the earlier fields once more for each default.
+                        FieldValues::BuilderState => quote! {
+                            let #maybe_mut #name = <
+                                #name_with_generics
+                                as
+                                #crate_module_path::NextFieldDefault<_>
+                            >::resolve((#(#values_of_deps,)* #name,));
+                        },
+                        FieldValues::Optional => quote! {
+                            let #maybe_mut #name = match #name {
+                                ::core::option::Option::Some(#name) => #name,
+                                ::core::option::Option::None => #default,
+                            };
+                        },
+                        FieldValues::Storage => quote! {
+                            let #maybe_mut #name = if __self.fields.#name.is_some() {
+                                #crate_module_path::__private::set_slot(__self.fields.#name.take())
+                            } else {
+                                #default
+                            };
+                        },
+                        FieldValues::RequiredOnly => quote!(let #maybe_mut #name = #default;),
                     }
                 } else {
-                    quote!(let #maybe_mut #name = #name.0;)
+                    match values {
+                        FieldValues::BuilderState => {
+                            let value = self.set_field_value(field, quote!(#name));
+                            quote!(let #maybe_mut #name = #value;)
+                        }
+                        FieldValues::Storage => quote! {
+                            let #maybe_mut #name = #crate_module_path::__private::set_slot(__self.fields.#name.take());
+                        },
+                        FieldValues::Optional | FieldValues::RequiredOnly => quote!(),
+                    }
                 }
             })
-            .collect::<Vec<_>>();
-        let field_names = self.fields.iter().map(|field| field.name);
+            .collect()
+    }
 
-        let build_method_name = self.build_method_name();
-        let build_method_visibility = self.build_method_visibility();
-        let build_method_doc = if self.builder_attr.doc {
-            self.builder_attr
-                .build_method
-                .common
-                .get_doc_or(|| format!("Finalise the builder and create its [`{}`] instance", name))
-        } else {
-            quote!()
-        };
+    /// For [`FieldValues::Optional`] mode - unwrap the required fields' `Option`s, or run `on_missing` if any of them is
+    /// `None`. `on_missing` can use `__missing`, which is the `MissingFields` error with the missing
+    /// required fields.
+    fn unwrap_required_fields(&self, on_missing: TokenStream) -> TokenStream {
+        let crate_module_path = &self.builder_attr.crate_module_path;
+        let required_fields = self.required_fields().collect::<Vec<_>>();
+        if required_fields.is_empty() {
+            return quote!();
+        }
+        let names = required_fields.iter().map(|f| f.name).collect::<Vec<_>>();
+        let name_strs = names.iter().map(|name| strip_raw_ident_prefix(name.to_string()));
+        let indices = (0..names.len()).map(proc_macro2::Literal::usize_unsuffixed);
+        let patterns = required_fields.iter().map(|f| {
+            let name = f.name;
+            let maybe_mut = f.maybe_mut();
+            quote!(::core::option::Option::Some(#maybe_mut #name))
+        });
+        quote! {
+            #[allow(unused_variables)]
+            let __missing = #crate_module_path::MissingFields::__new(
+                &[#(#name_strs,)*],
+                0 #(| (u128::from(#names.is_none()) << #indices))*,
+            );
+            let (#(#patterns,)*) = (#(#names,)*) else {
+                #on_missing
+            };
+        }
+    }
+    fn build_method_impl(&self) -> TokenStream {
+        let StructInfo {
+            ref name,
+            ref builder_name,
+            ..
+        } = *self;
 
-        let type_constructor = {
-            let ty_generics = ty_generics.as_turbofish();
-            quote!(#name #ty_generics)
+        // In step mode, the build method is only available in the final state, in which the optional fields are in their
+        // `Option` slots.
+        let step = self.builder_attr.step.is_some();
+        let generics = {
+            let mut generics = self.generics.clone();
+            for field in self.included_fields() {
+                if !step && (field.builder_attr.default.is_some() || field.set_by_preset) {
+                    let generic_param: syn::TypeParam = field.generic_ident.clone().into();
+                    generics.params.push(generic_param.into());
+                }
+            }
+            generics
+        };
+        let (impl_generics, _, _) = generics.split_for_impl();
+
+        let (_, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let slot_types = if step {
+            self.step_state(self.step_fields().count())
+        } else {
+            type_tuple(self.included_fields().map(|field| {
+                if field.builder_attr.default.is_some() || field.set_by_preset {
+                    field.type_ident()
+                } else {
+                    field.tuplized_type_ty_param()
+                }
+            }))
+        };
+        let modified_ty_generics = modify_types_generics_hack(&ty_generics, |args| {
+            args.push(syn::GenericArgument::Type(slot_types.clone().into()));
+        });
+
+        // In-place storage has the fields taken out of it one by one. `const` builders are then forgotten, since a
+        // `const fn` cannot drop them.
+        let (values, take_fields, forget_builder) = if self.in_place() {
+            (
+                FieldValues::Storage,
+                quote!(let mut __self = self;),
+                self.const_token().map(|_| {
+                    quote! {
+                        #[allow(clippy::forget_non_drop)]
+                        ::core::mem::forget(__self);
+                    }
+                }),
+            )
+        } else if step {
+            let destructuring = self.included_fields().map(|f| f.name);
+            let unwrap_step_fields = self.step_fields().map(|f| {
+                let name = f.name;
+                let maybe_mut = f.maybe_mut();
+                quote!(let #maybe_mut #name = #name.0;)
+            });
+            (
+                FieldValues::Optional,
+                quote! {
+                    let ( #(#destructuring,)* ) = self.fields;
+                    #(#unwrap_step_fields)*
+                },
+                None,
+            )
+        } else {
+            let destructuring = self.included_fields().map(|f| f.name);
+            (
+                FieldValues::BuilderState,
+                quote!(let ( #(#destructuring,)* ) = self.fields;),
+                None,
+            )
+        };
+        let where_clause_storage = self.where_clause_with(self.default_resolution_predicates(values));
+        let where_clause = where_clause_storage.as_ref().or(where_clause);
+        let assignments = self.default_resolution(values);
+        let field_names = self.fields.iter().map(|field| field.name);
+
+        let build_method_name = self.build_method_name();
+        let build_method_visibility = self.build_method_visibility();
+        let const_token = self.const_token();
+        // A `const fn` cannot call `Into::into` - and a `const` builder has no `build_method(into)` for it to do anything.
+        let into = const_token.is_none().then(|| quote!(.into()));
+        let build_method_doc = if self.builder_attr.doc {
+            self.builder_attr
+                .build_method
+                .common
+                .get_doc_or(|| format!("Finalise the builder and create its [`{}`] instance", name))
+        } else {
+            quote!()
+        };
+
+        let type_constructor = {
+            let ty_generics = ty_generics.as_turbofish();
+            quote!(#name #ty_generics)
         };
 
         let (build_method_generic, output_type, build_method_where_clause) = match &self.builder_attr.build_method.into {
@@ -784,32 +1996,685 @@ impl<'a> StructInfo<'a> {
             IntoSetting::TypeConversionToSpecificType(into) => (None, into.to_token_stream(), None),
         };
 
+        // With `build_method(impl_from)`, a complete builder can be converted into the type it builds, so that APIs that
+        // accept `impl Into<Foo>` can also accept the builder.
+        let from_builder_impl = if self.builder_attr.build_method.impl_from.is_some() {
+            let from_target = match &self.builder_attr.build_method.into {
+                IntoSetting::NoConversion | IntoSetting::GenericConversion => quote!(#name #ty_generics),
+                IntoSetting::TypeConversionToSpecificType(into) => into.to_token_stream(),
+            };
+            Some(quote! {
+                #[allow(dead_code, non_camel_case_types, missing_docs, clippy::ref_option_ref)]
+                #[automatically_derived]
+                impl #impl_generics ::core::convert::From<#builder_name #modified_ty_generics> for #from_target #where_clause {
+                    fn from(builder: #builder_name #modified_ty_generics) -> Self {
+                        builder.#build_method_name()
+                    }
+                }
+            })
+        } else {
+            None
+        };
+
         quote!(
             #[allow(dead_code, non_camel_case_types, missing_docs, clippy::ref_option_ref)]
             #[automatically_derived]
             impl #impl_generics #builder_name #modified_ty_generics #where_clause {
                 #build_method_doc
                 #[allow(clippy::default_trait_access, clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
-                #build_method_visibility fn #build_method_name #build_method_generic (self) -> #output_type #build_method_where_clause {
-                    let ( #(#destructuring,)* ) = self.fields;
+                #build_method_visibility #const_token fn #build_method_name #build_method_generic (self) -> #output_type #build_method_where_clause {
+                    #take_fields
                     #( #assignments )*
+                    #forget_builder
 
                     #[allow(deprecated)]
                     #type_constructor {
                         #( #field_names ),*
-                    }.into()
+                    }#into
                 }
             }
+            #from_builder_impl
         )
     }
 
+    /// Implement `Debug` for the builder, showing the values of the set fields and marking the unset ones.
+    fn debug_impl(&self) -> TokenStream {
+        let StructInfo { ref builder_name, .. } = *self;
+        let crate_module_path = &self.builder_attr.crate_module_path;
+
+        let mut generics = self.generics.clone();
+        let mut fields_tuple = empty_type_tuple();
+        for field in self.included_fields() {
+            generics.params.push(field.generic_ty_param());
+            fields_tuple.elems.push_value(field.type_ident());
+            fields_tuple.elems.push_punct(Default::default());
+            let type_ident = field.type_ident();
+            let bound: syn::WherePredicate = if self.in_place() {
+                let ty = field.ty;
+                parse_quote!(#type_ident: #crate_module_path::__private::DebugInPlaceSlot<#ty>)
+            } else {
+                parse_quote!(#type_ident: #crate_module_path::__private::DebugFieldSlot)
+            };
+            generics.make_where_clause().predicates.push(bound);
+        }
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let mut ty_generics = self.generic_arguments();
+        ty_generics.push(syn::GenericArgument::Type(fields_tuple.into()));
+
+        let destructuring = if self.in_place() {
+            None
+        } else {
+            let names = self.included_fields().map(|f| f.name);
+            Some(quote!(let ( #(#names,)* ) = &self.fields;))
+        };
+        let debug_fields = self.included_fields().map(|field| {
+            let name = field.name;
+            let name_str = strip_raw_ident_prefix(name.to_string());
+            let unset = if field.builder_attr.default.is_some() {
+                "<default>"
+            } else {
+                "<unset>"
+            };
+            let slot = if self.in_place() {
+                let (type_ident, ty) = (field.type_ident(), field.ty);
+                quote! {
+                    <#type_ident as #crate_module_path::__private::DebugInPlaceSlot<#ty>>::debug_in_place(
+                        &self.fields.#name,
+                        &#crate_module_path::__private::UnsetField(#unset),
+                    )
+                }
+            } else {
+                quote! {
+                    #crate_module_path::__private::DebugFieldSlot::debug_slot(
+                        #name,
+                        &#crate_module_path::__private::UnsetField(#unset),
+                    )
+                }
+            };
+            quote!(.field(#name_str, #slot))
+        });
+        let builder_name_str = builder_name.to_string();
+
+        quote! {
+            #[allow(dead_code, non_camel_case_types)]
+            #[automatically_derived]
+            impl #impl_generics ::core::fmt::Debug for #builder_name <#ty_generics> #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #destructuring
+                    f.debug_struct(#builder_name_str)
+                        #(#debug_fields)*
+                        .finish()
+                }
+            }
+        }
+    }
+
+    /// Generate a constructor that takes the required fields and sets all the other fields to their defaults.
+    fn constructor_impl(&self, settings: &CommonDeclarationSettings) -> syn::Result<TokenStream> {
+        let StructInfo { vis, name, .. } = *self;
+        let (impl_generics, ty_generics, _) = self.generics.split_for_impl();
+
+        let mut method_generics = syn::Generics::default();
+        let mut params = Vec::new();
+        let mut required_fields_init = Vec::new();
+        for field in self.setter_fields().filter(|f| f.builder_attr.default.is_none()) {
+            let SetterSignature {
+                generics,
+                params: field_params,
+                value,
+            } = field.setter_signature_with_unique_params()?;
+            if let Some(generics) = generics {
+                method_generics.params.extend(generics.params);
+                if let Some(where_clause) = generics.where_clause {
+                    method_generics.make_where_clause().predicates.extend(where_clause.predicates);
+                }
+            }
+            params.push(field_params);
+            let name = field.name;
+            let maybe_mut = field.maybe_mut();
+            required_fields_init.push(quote!(let #maybe_mut #name = #value;));
+        }
+        let method_where_clause = &method_generics.where_clause;
+
+        let where_clause = self.where_clause_with(self.default_resolution_predicates(FieldValues::RequiredOnly));
+        let assignments = self.default_resolution(FieldValues::RequiredOnly);
+        let field_names = self.fields.iter().map(|field| field.name);
+        let type_constructor = {
+            let ty_generics = ty_generics.as_turbofish();
+            quote!(#name #ty_generics)
+        };
+
+        let method_name = settings.get_name().unwrap_or_else(|| quote!(new));
+        let visibility = first_visibility(&[settings.vis.as_ref(), Some(vis)]);
+        let doc = settings.get_doc_or(|| {
+            format!(
+                "Create a [`{}`] from its required fields, with all the other fields set to their defaults.",
+                name
+            )
+        });
+
+        Ok(quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #doc
+                #[allow(clippy::default_trait_access, clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                #visibility fn #method_name #method_generics (#(#params),*) -> Self
+                #method_where_clause
+                {
+                    #(#required_fields_init)*
+                    #(#assignments)*
+
+                    #[allow(deprecated)]
+                    #type_constructor {
+                        #(#field_names),*
+                    }
+                }
+            }
+        })
+    }
+
+    /// Generate the method that creates a builder with the fields of the preset already set.
+    fn preset_impl(&self, preset: &Preset) -> TokenStream {
+        let StructInfo {
+            vis,
+            name,
+            ref builder_name,
+            ..
+        } = *self;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let is_preset = |field: &FieldInfo| {
+            let field_name = strip_raw_ident_prefix(field.name.to_string());
+            preset
+                .fields
+                .iter()
+                .any(|(preset_field, _)| strip_raw_ident_prefix(preset_field.to_string()) == field_name)
+        };
+        let crate_module_path = &self.builder_attr.crate_module_path;
+        // The fields the preset sets are moved to `Preset` slots, so that their setters can override them. In step mode,
+        // presets only set optional fields, which stay in their `Option` slots of the initial state.
+        let step = self.builder_attr.step.is_some();
+        let fields_type = if step {
+            self.step_state(0)
+        } else {
+            type_tuple(self.included_fields().map(|f| {
+                if is_preset(f) {
+                    let ty = f.ty;
+                    parse_quote!(#crate_module_path::__private::Preset<#ty>)
+                } else if f.builder_attr.via_mutators.is_some() {
+                    f.tuplized_type_ty_param()
+                } else {
+                    empty_type()
+                }
+            }))
+        };
+        let builder_generics = modify_types_generics_hack(&ty_generics, |args| {
+            args.push(syn::GenericArgument::Type(fields_type.clone().into()));
+        });
+
+        let setter_calls = preset.fields.iter().map(|(preset_field, value)| {
+            let preset_field_name = strip_raw_ident_prefix(preset_field.to_string());
+            let setter = self
+                .included_fields()
+                .find(|f| strip_raw_ident_prefix(f.name.to_string()) == preset_field_name)
+                .expect("preset fields were validated")
+                .setter_method_name();
+            quote!(.#setter(#value))
+        });
+
+        let names = self.included_fields().map(|f| f.name).collect::<Vec<_>>();
+        let preset_slots = self.included_fields().map(|f| {
+            let name = f.name;
+            if is_preset(f) {
+                quote!(#crate_module_path::__private::Preset(#name.0))
+            } else {
+                name.to_token_stream()
+            }
+        });
+
+        let preset_name = preset.name.as_ref().expect("presets must have names");
+        let initial_field_slots = self.included_fields().map(|f| self.initial_field_slot(f));
+        let initial_slot_types = if step {
+            fields_type.clone()
+        } else {
+            type_tuple(self.included_fields().map(|f| {
+                if f.builder_attr.via_mutators.is_some() {
+                    f.tuplized_type_ty_param()
+                } else {
+                    empty_type()
+                }
+            }))
+        };
+        let initial_builder = self.builder_from_slots(
+            &builder_name.clone().into(),
+            initial_field_slots,
+            &initial_slot_types,
+            quote!(::core::default::Default::default()),
+        );
+        let visibility = first_visibility(&[
+            self.builder_attr.builder_method.common.vis.as_ref(),
+            self.builder_attr.builder_type.common.vis.as_ref(),
+            Some(vis),
+        ]);
+        let doc = format!(
+            "Create a builder for building `{}`, with the `{}` preset's values for {}.",
+            name,
+            strip_raw_ident_prefix(preset_name.to_string()),
+            preset
+                .fields
+                .iter()
+                .map(|(field, _)| format!("`{}`", strip_raw_ident_prefix(field.to_string())))
+                .collect::<Vec<_>>()
+                .join(", "),
+        );
+
+        let body = if step {
+            quote! {
+                let __builder: #builder_name #builder_generics = #initial_builder;
+                __builder #(#setter_calls)*
+            }
+        } else if self.in_place() {
+            // With in-place storage, only the builder's type changes.
+            quote! {
+                let __builder = #initial_builder #(#setter_calls)*;
+                #builder_name {
+                    fields: __builder.fields,
+                    phantom: ::core::marker::PhantomData,
+                }
+            }
+        } else {
+            quote! {
+                let #builder_name {
+                        fields: ( #(#names,)* ),
+                        phantom: __phantom,
+                    } = #initial_builder
+                    #(#setter_calls)*;
+                #builder_name {
+                    fields: ( #(#preset_slots,)* ),
+                    phantom: __phantom,
+                }
+            }
+        };
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #[doc = #doc]
+                #[allow(dead_code, deprecated, clippy::default_trait_access)]
+                #visibility fn #preset_name() -> #builder_name #builder_generics {
+                    #body
+                }
+            }
+        }
+    }
+
+    /// Implement serde's `Deserialize` for the struct, by deserializing all the fields into `Option`s and then resolving
+    /// them the same way `build()` does.
+    fn deserialize_impl(&self) -> TokenStream {
+        let StructInfo { vis, name, generics, .. } = *self;
+        let crate_module_path = &self.builder_attr.crate_module_path;
+        let serde = quote!(#crate_module_path::__private::serde);
+        let serde_crate = serde.to_string();
+        let name_str = strip_raw_ident_prefix(name.to_string());
+        let (_, ty_generics, where_clause) = generics.split_for_impl();
+
+        let mut bounds = Vec::new();
+        let mut conversions = Vec::new();
+        let helper_fields = self
+            .included_fields()
+            .map(|field| {
+                let field_name = field.name;
+                if field.builder_attr.via_mutators.is_some() {
+                    let field_ty = field.ty;
+                    return quote! {
+                        #[serde(skip)]
+                        #field_name: ::core::option::Option<#field_ty>
+                    };
+                }
+                let (deserialized_ty, conversion) = field.dynamic_input(false);
+                if let Some(conversion) = conversion {
+                    conversions.push(quote!(let #field_name = #field_name.map(#conversion);));
+                }
+                bounds.push(quote!(#deserialized_ty: #serde::Deserialize<'de>).to_string());
+                quote!(#field_name: ::core::option::Option<#deserialized_ty>)
+            })
+            .collect::<Vec<_>>();
+        let bounds = bounds.join(", ");
+        let phantom = phantom_data_for_generics(generics);
+
+        let de_generics = {
+            let mut generics = generics.clone();
+            generics.params.insert(0, parse_quote!('de));
+            generics
+        };
+        let (de_impl_generics, _, _) = de_generics.split_for_impl();
+        let mut predicates = vec![parse_quote!(__TypedBuilderDeserialize #ty_generics: #serde::Deserialize<'de>)];
+        predicates.extend(self.default_resolution_predicates(FieldValues::Optional));
+        let de_where_clause = self.where_clause_with(predicates);
+
+        let helper_field_names = self.included_fields().map(|field| field.name);
+        let unwrap_required_fields = self.unwrap_required_fields(quote! {
+            return ::core::result::Result::Err(<__D::Error as #serde::de::Error>::custom(
+                __missing,
+            ));
+        });
+        let assignments = self.default_resolution(FieldValues::Optional);
+        let field_names = self.fields.iter().map(|field| field.name);
+        let type_constructor = {
+            let ty_generics = ty_generics.as_turbofish();
+            quote!(#name #ty_generics)
+        };
+
+        quote! {
+            const _: () = {
+                #[derive(#serde::Deserialize)]
+                #[serde(crate = #serde_crate, rename = #name_str, bound(deserialize = #bounds))]
+                #[allow(dead_code, non_camel_case_types)]
+                #vis struct __TypedBuilderDeserialize #generics #where_clause {
+                    #(#helper_fields,)*
+                    #[serde(skip)]
+                    __typed_builder_phantom: #phantom,
+                }
+
+                #[automatically_derived]
+                impl #de_impl_generics #serde::Deserialize<'de> for #name #ty_generics #de_where_clause {
+                    #[allow(clippy::default_trait_access, clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                    fn deserialize<__D: #serde::Deserializer<'de>>(deserializer: __D) -> ::core::result::Result<Self, __D::Error> {
+                        let __TypedBuilderDeserialize { #(#helper_field_names,)* .. } =
+                            <__TypedBuilderDeserialize #ty_generics as #serde::Deserialize<'de>>::deserialize(deserializer)?;
+                        #(#conversions)*
+                        #unwrap_required_fields
+                        #(#assignments)*
+
+                        #[allow(deprecated)]
+                        ::core::result::Result::Ok(#type_constructor {
+                            #(#field_names),*
+                        })
+                    }
+                }
+            };
+        }
+    }
+
+    /// Generate a `from_env()` method that reads the fields from environment variables and resolves them the same way
+    /// `build()` does.
+    fn env_constructor_impl(&self, prefix: &str) -> TokenStream {
+        let StructInfo { vis, name, generics, .. } = *self;
+        let crate_module_path = &self.builder_attr.crate_module_path;
+        let (impl_generics, ty_generics, _) = generics.split_for_impl();
+
+        let mut predicates = self.default_resolution_predicates(FieldValues::Optional);
+        let mut var_names = Vec::new();
+        let reads = self
+            .included_fields()
+            .map(|field| {
+                let field_name = field.name;
+                if field.builder_attr.via_mutators.is_some() {
+                    return quote!(let #field_name = ::core::option::Option::None;);
+                }
+                let var_name = field.env_var_name(prefix);
+                let required = field.builder_attr.default.is_none();
+                let (parsed_ty, conversion) = field.dynamic_input(true);
+                predicates.push(parse_quote!(#parsed_ty: ::core::str::FromStr));
+                predicates.push(parse_quote!(<#parsed_ty as ::core::str::FromStr>::Err: ::core::fmt::Display));
+                let conversion = conversion.map(|conversion| quote!(.map(#conversion)));
+                let read = quote! {
+                    let #field_name = #crate_module_path::__private::env_var::<#parsed_ty>(#var_name, #required, &mut __errors)#conversion;
+                };
+                var_names.push(var_name);
+                read
+            })
+            .collect::<Vec<_>>();
+        let where_clause = self.where_clause_with(predicates);
+
+        let unwrap_required_fields = self.unwrap_required_fields(quote!(return ::core::result::Result::Err(__errors);));
+        let assignments = self.default_resolution(FieldValues::Optional);
+        let field_names = self.fields.iter().map(|field| field.name);
+        let type_constructor = {
+            let ty_generics = ty_generics.as_turbofish();
+            quote!(#name #ty_generics)
+        };
+
+        let doc = format!(
+            "Create a [`{}`] from the environment variables {}.",
+            name,
+            var_names
+                .iter()
+                .map(|var_name| format!("`{var_name}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #name #ty_generics #where_clause {
+                #[doc = #doc]
+                #[allow(clippy::default_trait_access, clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                #vis fn from_env() -> ::core::result::Result<Self, #crate_module_path::EnvError> {
+                    let mut __errors = <#crate_module_path::EnvError as ::core::default::Default>::default();
+                    #(#reads)*
+                    if !__errors.missing.is_empty() || !__errors.invalid.is_empty() {
+                        return ::core::result::Result::Err(__errors);
+                    }
+                    #unwrap_required_fields
+                    #(#assignments)*
+
+                    #[allow(deprecated)]
+                    ::core::result::Result::Ok(#type_constructor {
+                        #(#field_names),*
+                    })
+                }
+            }
+        }
+    }
+
+    /// Generate a companion struct with an `Option` for each field that has a setter. Patches can be merged, with the
+    /// later ones overriding the earlier ones, and then built by resolving the fields the same way `build()` does.
+    fn patch_impl(&self, settings: &PatchTypeSettings) -> TokenStream {
+        let StructInfo { vis, name, generics, .. } = *self;
+        let crate_module_path = &self.builder_attr.crate_module_path;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let patch_name = syn::Ident::new(
+            &settings
+                .common
+                .get_name()
+                .map(|name| strip_raw_ident_prefix(name.to_string()))
+                .unwrap_or_else(|| strip_raw_ident_prefix(format!("{}Patch", name))),
+            proc_macro2::Span::call_site(),
+        );
+        let patch_vis = first_visibility(&[settings.common.vis.as_ref(), Some(vis)]);
+        let patch_doc = settings.common.get_doc_or(|| {
+            format!(
+                "
+                A partial [`{name}`], with every field optional.
+
+                Combine patches with [`{patch_name}::merge`], and create the [`{name}`] with [`{patch_name}::build`].
+                "
+            )
+        });
+        let patch_attributes = &settings.attributes;
+
+        // Fields that are only set by mutators are not part of the patch, and start from their `via_mutators` init.
+        let patch_fields = self
+            .included_fields()
+            .filter(|f| f.builder_attr.via_mutators.is_none())
+            .collect::<Vec<_>>();
+        let names = patch_fields.iter().map(|f| f.name).collect::<Vec<_>>();
+        let declarations = patch_fields.iter().map(|f| {
+            let FieldInfo { name, ty, .. } = f;
+            let field_vis = f.setter_vis();
+            let doc = f.builder_attr.doc_comments.iter().map(|&line| quote!(#[doc = #line]));
+            quote! {
+                #(#doc)*
+                #field_vis #name: ::core::option::Option<#ty>
+            }
+        });
+        let via_mutators = self
+            .included_fields()
+            .filter(|f| f.builder_attr.via_mutators.is_some())
+            .map(|f| {
+                let name = f.name;
+                quote!(let #name = ::core::option::Option::None;)
+            });
+
+        // The struct's generic parameters may only be used by fields that are not part of the patch.
+        let (phantom_declaration, phantom_init, phantom_pattern) = if generics.params.is_empty() {
+            (None, None, None)
+        } else {
+            let phantom = phantom_data_for_generics(generics);
+            (
+                Some(quote!(#[doc(hidden)] pub __phantom: #phantom,)),
+                Some(quote!(__phantom: ::core::marker::PhantomData,)),
+                Some(quote!(..)),
+            )
+        };
+
+        let error_type = quote!(#crate_module_path::MissingFields);
+        let build_predicates = self.default_resolution_predicates(FieldValues::Optional);
+        let build_where_clause = (!build_predicates.is_empty()).then(|| quote!(where #(#build_predicates),*));
+        let unwrap_required_fields = self.unwrap_required_fields(quote! {
+            return ::core::result::Result::Err(__missing);
+        });
+        let assignments = self.default_resolution(FieldValues::Optional);
+        let field_names = self.fields.iter().map(|field| field.name);
+        let type_constructor = {
+            let ty_generics = ty_generics.as_turbofish();
+            quote!(#name #ty_generics)
+        };
+        let build_doc = format!(
+            "Create the [`{name}`], using the defaults of the fields that are not set. Fails with the required fields that \
+             are not set."
+        );
+
+        quote! {
+            #patch_doc
+            #(#patch_attributes)*
+            #patch_vis struct #patch_name #generics #where_clause {
+                #(#declarations,)*
+                #phantom_declaration
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::default::Default for #patch_name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self {
+                        #(#names: ::core::option::Option::None,)*
+                        #phantom_init
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #patch_name #ty_generics #where_clause {
+                /// Combine two patches. The fields that `other` sets override the ones that `self` sets.
+                #[must_use]
+                #patch_vis fn merge(self, other: Self) -> Self {
+                    Self {
+                        #(#names: ::core::option::Option::or(other.#names, self.#names),)*
+                        #phantom_init
+                    }
+                }
+
+                #[doc = #build_doc]
+                #[allow(clippy::default_trait_access, clippy::used_underscore_binding, clippy::no_effect_underscore_binding)]
+                #patch_vis fn build(self) -> ::core::result::Result<#name #ty_generics, #error_type> #build_where_clause {
+                    let Self { #(#names,)* #phantom_pattern } = self;
+                    #(#via_mutators)*
+                    #unwrap_required_fields
+                    #(#assignments)*
+
+                    #[allow(deprecated)]
+                    ::core::result::Result::Ok(#type_constructor {
+                        #(#field_names),*
+                    })
+                }
+            }
+        }
+    }
+
+    /// With `external_mutators`, a `macro_rules!` that passes the struct definition to `__mutators_impl`, so that
+    /// `#[typed_builder::mutators]` can generate mutators from outside the derive. The builder's fields stay private, so the
+    /// generated mutators take the fields out of the builder and put them back with hidden methods - which, like the
+    /// setters, can only create builders whose set fields hold values.
+    fn external_mutators_callback(&self) -> TokenStream {
+        if self.builder_attr.external_mutators.is_none() {
+            return quote!();
+        }
+        let StructInfo { ref builder_name, .. } = *self;
+        let macro_name = external_mutators_macro_name(self.name);
+        let crate_module_path = &self.builder_attr.crate_module_path;
+        let input = self.input;
+        let mut generics = self.generics.clone();
+        generics.params.push(parse_quote!(TypedBuilderFields));
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+        let mut ty_generics = self.generic_arguments();
+        ty_generics.push(parse_quote!(TypedBuilderFields));
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics #builder_name <#ty_generics> #where_clause {
+                #[doc(hidden)]
+                #[allow(dead_code)]
+                pub(crate) fn __into_typed_builder_fields(self) -> TypedBuilderFields {
+                    self.fields
+                }
+
+                #[doc(hidden)]
+                #[allow(dead_code, clippy::default_trait_access)]
+                pub(crate) fn __from_typed_builder_fields(fields: TypedBuilderFields) -> Self {
+                    Self {
+                        fields,
+                        phantom: ::core::default::Default::default(),
+                    }
+                }
+            }
+
+            #[doc(hidden)]
+            #[allow(unused_macros)]
+            macro_rules! #macro_name {
+                ($($mutators:tt)*) => {
+                    #crate_module_path::__mutators_impl! {
+                        { #input }
+                        $($mutators)*
+                    }
+                };
+            }
+            #[doc(hidden)]
+            #[allow(unused_imports)]
+            pub(crate) use #macro_name;
+        }
+    }
+
+    /// Generate the mutators defined in an `impl` block marked with `#[typed_builder::mutators]`.
+    pub fn derive_external_mutators(&self, mutators: &[Mutator], module_path: Option<syn::Path>) -> syn::Result<TokenStream> {
+        let mut builder_path = module_path.unwrap_or_else(|| syn::Path {
+            leading_colon: None,
+            segments: Default::default(),
+        });
+        builder_path.segments.push(self.builder_name.clone().into());
+        mutators.iter().map(|m| self.mutator_impl(m, &builder_path, true)).collect()
+    }
+
     pub fn derive(&self) -> syn::Result<TokenStream> {
         let builder_creation = self.builder_creation_impl()?;
 
-        let fields = self
+        let fields = if self.builder_attr.step.is_some() {
+            self.step_impls()?
+        } else {
+            self.setter_fields()
+                .map(|f| self.field_impl(f, None))
+                .collect::<Result<TokenStream, _>>()?
+        };
+
+        let resets = self
             .setter_fields()
-            .map(|f| self.field_impl(f))
-            .collect::<Result<TokenStream, _>>()?;
+            .filter(|f| self.builder_attr.step.is_none() && f.builder_attr.setter.resettable.is_some())
+            .map(|f| self.reset_impl(f, None));
+
+        let getters = self
+            .included_fields()
+            .filter(|f| f.builder_attr.getters.is_some())
+            .map(|f| self.getters_impl(f));
 
         let next_field_default_impls = self
             .fields
@@ -827,18 +2692,50 @@ impl<'a> StructInfo<'a> {
             .iter()
             .flat_map(|f| &f.builder_attr.mutators)
             .chain(&self.builder_attr.mutators)
-            .map(|m| self.mutator_impl(m))
+            .map(|m| self.mutator_impl(m, &self.builder_name.clone().into(), false))
             .collect::<Result<TokenStream, _>>()?;
 
         let build_method = self.build_method_impl();
 
+        let debug = self.builder_attr.builder_type.debug.map(|_| self.debug_impl());
+
+        let constructor = self
+            .builder_attr
+            .constructor
+            .as_ref()
+            .map(|settings| self.constructor_impl(settings))
+            .transpose()?;
+
+        let presets = self.builder_attr.presets.iter().map(|preset| self.preset_impl(preset));
+
+        let deserialize = self.builder_attr.deserialize.map(|_| self.deserialize_impl());
+
+        let from_env = self
+            .builder_attr
+            .from_env
+            .as_ref()
+            .map(|from_env| self.env_constructor_impl(&from_env.prefix));
+
+        let patch = self.builder_attr.patch.as_ref().map(|settings| self.patch_impl(settings));
+
+        let external_mutators_callback = self.external_mutators_callback();
+
         Ok(quote! {
             #builder_creation
             #fields
+            #(#resets)*
+            #(#getters)*
             #next_field_default_impls
             #(#required_fields)*
             #mutators
             #build_method
+            #debug
+            #constructor
+            #(#presets)*
+            #deserialize
+            #from_env
+            #patch
+            #external_mutators_callback
         })
     }
 }
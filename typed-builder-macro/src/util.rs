@@ -3,7 +3,9 @@ use std::iter;
 use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::{ToTokens, format_ident, quote};
 use syn::{
-    Attribute, Error, Pat, PatIdent, Token, parenthesized,
+    Attribute, Error, Pat, PatIdent, Token,
+    ext::IdentExt,
+    parenthesized,
     parse::{Parse, ParseStream, Parser},
     punctuated::Punctuated,
     spanned::Spanned,
@@ -79,6 +81,67 @@ where
     abga
 }
 
+/// Whether the identifier appears anywhere in the tokens - used for finding which types use a type parameter.
+pub fn tokens_mention_ident(tokens: &impl ToTokens, ident: &Ident) -> bool {
+    fn mentions(tokens: TokenStream, ident: &Ident) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(token_ident) => token_ident == *ident,
+            TokenTree::Group(group) => mentions(group.stream(), ident),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+        })
+    }
+    mentions(tokens.to_token_stream(), ident)
+}
+
+/// Replaces a type parameter with another type, in both types and paths (e.g. `T::Assoc` or `T::new()`)
+pub struct ReplaceTypeParam<'a> {
+    pub param: &'a Ident,
+    pub with: &'a syn::Type,
+}
+
+impl ReplaceTypeParam<'_> {
+    fn replace_in_path(&self, qself: &mut Option<syn::QSelf>, path: &mut syn::Path) {
+        if qself.is_some() || path.leading_colon.is_some() || path.segments.len() < 2 {
+            return;
+        }
+        let first = &path.segments[0];
+        if first.ident != *self.param || !first.arguments.is_none() {
+            return;
+        }
+        *qself = Some(syn::QSelf {
+            lt_token: Default::default(),
+            ty: Box::new(self.with.clone()),
+            position: 0,
+            as_token: None,
+            gt_token: Default::default(),
+        });
+        path.segments = path.segments.iter().skip(1).cloned().collect();
+        path.leading_colon = Some(Default::default());
+    }
+}
+
+impl syn::visit_mut::VisitMut for ReplaceTypeParam<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Path(syn::TypePath { qself: None, path }) = ty
+            && path.is_ident(self.param)
+        {
+            *ty = self.with.clone();
+            return;
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
+
+    fn visit_type_path_mut(&mut self, type_path: &mut syn::TypePath) {
+        self.replace_in_path(&mut type_path.qself, &mut type_path.path);
+        syn::visit_mut::visit_type_path_mut(self, type_path);
+    }
+
+    fn visit_expr_path_mut(&mut self, expr_path: &mut syn::ExprPath) {
+        self.replace_in_path(&mut expr_path.qself, &mut expr_path.path);
+        syn::visit_mut::visit_expr_path_mut(self, expr_path);
+    }
+}
+
 pub fn strip_raw_ident_prefix(mut name: String) -> String {
     if name.starts_with("r#") {
         name.replace_range(0..2, "");
@@ -86,6 +149,11 @@ pub fn strip_raw_ident_prefix(mut name: String) -> String {
     name
 }
 
+/// The name of the `macro_rules!` callback that `#[typed_builder::mutators(...)]` uses to get the struct definition
+pub fn external_mutators_macro_name(struct_name: &syn::Ident) -> syn::Ident {
+    format_ident!("__typed_builder_mutators_{}", strip_raw_ident_prefix(struct_name.to_string()))
+}
+
 pub fn first_visibility(visibilities: &[Option<&syn::Visibility>]) -> proc_macro2::TokenStream {
     let vis = visibilities
         .iter()
@@ -317,13 +385,14 @@ impl Parse for AttrArg {
             return Ok(Self::Fn(input.parse()?));
         }
 
+        // Names may be keywords, like `const`
         if input.peek(Token![!]) {
             Ok(Self::Not {
                 not: input.parse()?,
-                name: input.parse()?,
+                name: input.call(Ident::parse_any)?,
             })
         } else {
-            let name = input.parse()?;
+            let name = input.call(Ident::parse_any)?;
             if input.peek(Token![,]) || input.is_empty() {
                 Ok(Self::Flag(name))
             } else if input.peek(token::Paren) {